@@ -0,0 +1,187 @@
+//! A global allocator that counts what is allocated, so the runner and benches
+//! can report the allocations of every part next to its timing.
+//!
+//! It only counts once registered, which the runner and the benches of every
+//! day do behind their `count-allocs` feature:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::new();
+//! ```
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+};
+
+use crate::{Part, Solution};
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts allocations, allocated bytes and the size of the heap, then
+/// forwards to the system allocator.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+/// What was allocated while running something, see [`count`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocs {
+    /// Allocations, a reallocation counting as one.
+    pub count: u64,
+    /// Bytes asked for by those allocations.
+    pub bytes: u64,
+    /// Most the heap grew above its size when counting started.
+    pub peak: u64,
+}
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    fn track_alloc(size: usize) {
+        ACTIVE.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::track_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::track_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::track_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f`, counting what it allocates if a [`CountingAllocator`] is the
+/// global allocator.
+///
+/// Allocations of other threads are counted too, so this is only exact when
+/// nothing else runs at the same time.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Allocs>) {
+    if !ACTIVE.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+
+    let value = f();
+
+    let allocs = Allocs {
+        count: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(current) as u64,
+    };
+    (value, Some(allocs))
+}
+
+/// Prints what parsing `input` and solving each part of it allocate once, as
+/// `day_01_bench allocs part1 2 48 48` lines of count, bytes and peak, which
+/// `aoc bench` stores next to the timings divan prints for `bench`.
+///
+/// Prints nothing if no [`CountingAllocator`] is the global allocator.
+pub fn report<S: Solution>(bench: &str, input: &str) {
+    let (parsed, Some(allocs)) = count(|| S::parse(input).expect("should parse the input")) else {
+        return;
+    };
+    println!("{bench} allocs parse {allocs:#}");
+    for part in Part::ALL {
+        if let (_, Some(allocs)) = count(|| S::part(&parsed, part)) {
+            println!("{bench} allocs part{part} {allocs:#}");
+        }
+    }
+}
+
+/// `3 allocs, 1.5 KiB, peak 512 B`, or with `{:#}` the bare numbers
+/// `3 1536 512` for tools to read.
+impl Display for Allocs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{} {} {}", self.count, self.bytes, self.peak);
+        }
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes < 1 << 10 => format!("{bytes} B"),
+        bytes if bytes < 1 << 20 => format!("{:.1} KiB", bytes as f64 / (1 << 10) as f64),
+        bytes if bytes < 1 << 30 => format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64),
+        bytes => format!("{:.1} GiB", bytes as f64 / (1 << 30) as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator::new();
+
+    #[test]
+    fn test_count() {
+        // other tests allocate at the same time, so only lower bounds hold
+        let (v, allocs) = count(|| {
+            let mut v = Vec::with_capacity(1024);
+            v.extend(std::iter::repeat_n(1u8, 2048));
+            v
+        });
+        let allocs = allocs.expect("the allocator should be counting");
+        assert_eq!(2048, v.len());
+        assert!(allocs.count >= 2);
+        assert!(allocs.bytes >= 1024 + 2048);
+        assert!(allocs.peak >= 2048);
+    }
+
+    #[test]
+    fn test_display() {
+        let allocs = Allocs {
+            count: 3,
+            bytes: 1536,
+            peak: 512,
+        };
+        assert_eq!("3 allocs, 1.5 KiB, peak 512 B", allocs.to_string());
+        assert_eq!("3 1536 512", format!("{allocs:#}"));
+    }
+}
@@ -0,0 +1,189 @@
+//! Cycle detection for simulations that step from one state to the next, so
+//! they can tell when they loop and skip ahead over repeated periods.
+//!
+//! A simulation that can end, like a guard walking off the map, can step an
+//! `Option` of its state that stays `None` once it ended, the cycle is then
+//! on `None` if it ends.
+use std::{collections::HashMap, hash::Hash, iter};
+
+/// Where the states of a simulation start repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// Steps before the first state of the cycle.
+    pub start: usize,
+    /// Steps before the cycle comes back to its first state.
+    pub len: usize,
+    /// First state of the cycle, the state after `start` steps.
+    pub state: S,
+}
+
+impl<S> Cycle<S> {
+    /// Earliest step that has the same state as step `k`.
+    pub fn equivalent_step(&self, k: usize) -> usize {
+        if k < self.start {
+            k
+        } else {
+            self.start + (k - self.start) % self.len
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, comparing states but never
+/// storing more than two of them.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    // find the length by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // with the hare `len` steps ahead, they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle {
+        start,
+        len,
+        state: tortoise,
+    }
+}
+
+/// Finds the cycle with Floyd's algorithm, comparing states but never
+/// storing more than two of them.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    // the hare moves twice as fast, they meet a multiple of the length in
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+
+    Cycle {
+        start,
+        len,
+        state: tortoise,
+    }
+}
+
+/// Finds the cycle by remembering the step of every state until one comes
+/// back, which takes the fewest steps when they are expensive.
+pub fn first_repeat<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Cycle {
+                start,
+                len: i - start,
+                state,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!("a simulation should repeat before running out of steps")
+}
+
+/// State after `k` steps, only simulating up to the first repeat of the
+/// cycle.
+pub fn state_at<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S, k: usize) -> S {
+    let cycle = brent(initial.clone(), &mut step);
+    let k = cycle.equivalent_step(k);
+    if k >= cycle.start {
+        return steps(cycle.state, step).nth(k - cycle.start).unwrap();
+    }
+    steps(initial, step).nth(k).unwrap()
+}
+
+/// The states of a simulation, starting with `initial`.
+pub fn steps<S: Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> impl Iterator<Item = S> {
+    iter::successors(Some(initial), move |state| Some(step(state)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Goes 0, 1, ... 9 then loops over 3..10.
+    fn step(x: &u32) -> u32 {
+        if *x == 9 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let cycle = Cycle {
+            start: 3,
+            len: 7,
+            state: 3,
+        };
+        assert_eq!(cycle, brent(0, step));
+        assert_eq!(cycle, floyd(0, step));
+        assert_eq!(cycle, first_repeat(0, step));
+
+        let pure = Cycle {
+            start: 0,
+            len: 7,
+            state: 5,
+        };
+        assert_eq!(pure, brent(5, step));
+        assert_eq!(pure, floyd(5, step));
+        assert_eq!(pure, first_repeat(5, step));
+    }
+
+    #[test]
+    fn test_state_at() {
+        for k in [0, 2, 3, 9, 10, 1_000_000_000] {
+            let expected = if k < 10 {
+                k as u32
+            } else {
+                3 + (k as u32 - 3) % 7
+            };
+            assert_eq!(expected, state_at(0, step, k));
+        }
+
+        // a simulation that ends loops on `None`
+        let ending = |x: &Option<u32>| x.and_then(|x| (x < 5).then_some(x + 1));
+        let cycle = brent(Some(0), ending);
+        assert_eq!((6, 1, None), (cycle.start, cycle.len, cycle.state));
+    }
+}
@@ -0,0 +1,346 @@
+//! Loads puzzle inputs at runtime, so a checkout without inputs still builds.
+//!
+//! The input of a day is searched in this order:
+//! 1. the path given with `--input <path>`, where `-` reads from stdin
+//! 2. `day-NN/input.txt` (or the older `day-NN/input1.txt`)
+//! 3. `inputs/<year>/day-NN.enc` in the encrypted store, see [`crate::store`]
+//! 4. `<cache>/<year>/day-NN/input.txt`, see [`cache_dir`]
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::store::{self, stored_input_path, Secret, StoreError, STORE_DIR};
+
+/// Builds an [`Input`] for the day crate this macro is called from, of the
+/// given year or [`YEAR`](crate::YEAR).
+#[macro_export]
+macro_rules! input {
+    () => {
+        $crate::input!($crate::YEAR)
+    };
+    ($year:expr) => {
+        $crate::input::Input::new($year, {
+            const DAY: u8 = $crate::input::day_from_crate_name(env!("CARGO_PKG_NAME"));
+            DAY
+        })
+        .with_day_dir(env!("CARGO_MANIFEST_DIR"))
+    };
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// read the puzzle input from this file instead
+    /// of searching for it, `-` reads it from stdin
+    #[clap(long)]
+    pub input: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Cli {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    year: u16,
+    day: u8,
+    path: Option<PathBuf>,
+    day_dir: Option<PathBuf>,
+    store_dir: Option<PathBuf>,
+    secret: Option<Secret>,
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum InputError {
+    #[error("no input found for {year} day {day}")]
+    #[diagnostic(code(aoc::input::missing))]
+    Missing {
+        year: u16,
+        day: u8,
+        #[help]
+        help: String,
+    },
+    #[error("failed to read input {}", path.display())]
+    #[diagnostic(code(aoc::input::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to decrypt input {}", path.display())]
+    #[diagnostic(code(aoc::input::decrypt))]
+    Decrypt {
+        path: PathBuf,
+        #[source]
+        #[diagnostic_source]
+        source: StoreError,
+    },
+    #[error("failed to read input from stdin")]
+    #[diagnostic(code(aoc::input::stdin))]
+    Stdin(#[source] io::Error),
+}
+
+impl Input {
+    pub fn new(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            path: None,
+            day_dir: None,
+            store_dir: None,
+            secret: None,
+            cache_dir: cache_dir(),
+        }
+    }
+
+    /// Reads the input from `path` instead of searching for it, `-` reads stdin.
+    pub fn with_path(mut self, path: Option<PathBuf>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Takes the path from the `--input` command line argument, if present.
+    pub fn with_args(self) -> Self {
+        self.with_path(Cli::parse().input.input)
+    }
+
+    /// Looks for the input in `day_dir`, and in the store of the workspace
+    /// `day_dir` belongs to unless another store was chosen.
+    pub fn with_day_dir(mut self, day_dir: impl Into<PathBuf>) -> Self {
+        let day_dir = day_dir.into();
+        if self.store_dir.is_none() {
+            self.store_dir = day_dir.parent().map(|workspace| workspace.join(STORE_DIR));
+        }
+        self.day_dir = Some(day_dir);
+        self
+    }
+
+    pub fn with_store_dir(mut self, store_dir: Option<PathBuf>) -> Self {
+        self.store_dir = store_dir;
+        self
+    }
+
+    /// Decrypts the store with `secret` instead of the one in the environment.
+    pub fn with_secret(mut self, secret: Option<Secret>) -> Self {
+        self.secret = secret;
+        self
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
+    /// Every file that is looked at when no path was given, in order.
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        let mut candidates = vec![];
+        if let Some(day_dir) = &self.day_dir {
+            candidates.push(day_dir.join("input.txt"));
+            candidates.push(day_dir.join("input1.txt"));
+        }
+        if let Some(store_dir) = &self.store_dir {
+            candidates.push(stored_input_path(store_dir, self.year, self.day));
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            candidates.push(cached_input_path(cache_dir, self.year, self.day));
+        }
+        candidates
+    }
+
+    pub fn load(&self) -> Result<String, InputError> {
+        match &self.path {
+            Some(path) if path == Path::new("-") => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Some(path) => read(path),
+            None => {
+                let candidates = self.candidates();
+                match candidates.iter().find(|path| path.is_file()) {
+                    Some(path) if self.is_stored(path) => self.decrypt(path),
+                    Some(path) => read(path),
+                    None => Err(InputError::Missing {
+                        year: self.year,
+                        day: self.day,
+                        help: format!(
+                            "fetch it with `just get-input day-{:02}` or pass `--input <path>`, searched:\n{}",
+                            self.day,
+                            candidates
+                                .iter()
+                                .map(|path| format!("  {}", path.display()))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+impl Input {
+    fn is_stored(&self, path: &Path) -> bool {
+        self.store_dir
+            .as_ref()
+            .is_some_and(|store_dir| path == stored_input_path(store_dir, self.year, self.day))
+    }
+
+    fn decrypt(&self, path: &Path) -> Result<String, InputError> {
+        let decrypt_err = |source| InputError::Decrypt {
+            path: path.to_path_buf(),
+            source,
+        };
+        let secret = match &self.secret {
+            Some(secret) => secret.clone(),
+            None => Secret::from_env()
+                .map_err(decrypt_err)?
+                .ok_or(StoreError::NoKey)
+                .map_err(decrypt_err)?,
+        };
+        let stored = fs::read(path).map_err(|source| InputError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        store::decrypt(&secret, self.year, self.day, &stored).map_err(decrypt_err)
+    }
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Directory where fetched inputs are cached, taken from `AOC_CACHE_DIR`,
+/// then `$XDG_CACHE_HOME/aoc` and finally `$HOME/.cache/aoc`.
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return Some(dir.into());
+    }
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("aoc"));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("aoc"))
+}
+
+/// Directory holding everything cached for a day, `<cache>/<year>/day-NN`.
+pub fn cached_day_dir(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_dir
+        .join(year.to_string())
+        .join(format!("day-{day:02}"))
+}
+
+pub fn cached_input_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cached_day_dir(cache_dir, year, day).join("input.txt")
+}
+
+/// Parses the day out of a crate name like `day-01`.
+pub const fn day_from_crate_name(name: &str) -> u8 {
+    match name.as_bytes() {
+        [b'd', b'a', b'y', b'-', tens @ b'0'..=b'9', ones @ b'0'..=b'9'] => {
+            (*tens - b'0') * 10 + (*ones - b'0')
+        }
+        _ => panic!("day crates must be named `day-NN`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(dir: &Path) -> Input {
+        Input::new(2024, 1)
+            .with_day_dir(dir.join("day-01"))
+            .with_cache_dir(Some(dir.join("cache")))
+            .with_secret(Some(Secret::new("secret")))
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_search_order() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let input = input(dir.path());
+
+        write(
+            &cached_input_path(&dir.path().join("cache"), 2024, 1),
+            "cache",
+        );
+        assert_eq!("cache", input.load()?);
+
+        let stored = stored_input_path(&dir.path().join(STORE_DIR), 2024, 1);
+        fs::create_dir_all(stored.parent().unwrap()).unwrap();
+        fs::write(
+            &stored,
+            store::encrypt(&Secret::new("secret"), 2024, 1, "store")?,
+        )
+        .unwrap();
+        assert_eq!("store", input.load()?);
+
+        write(&dir.path().join("day-01/input1.txt"), "legacy");
+        assert_eq!("legacy", input.load()?);
+
+        write(&dir.path().join("day-01/input.txt"), "day");
+        assert_eq!("day", input.load()?);
+
+        write(&dir.path().join("other.txt"), "explicit");
+        let input = input.with_path(Some(dir.path().join("other.txt")));
+        assert_eq!("explicit", input.load()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_lists_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let Err(InputError::Missing { day, help, .. }) = input(dir.path()).load() else {
+            panic!("input should be missing");
+        };
+        assert_eq!(1, day);
+        assert!(help.contains("just get-input day-01"));
+        assert!(help.contains(&dir.path().join("day-01/input.txt").display().to_string()));
+    }
+
+    #[test]
+    fn test_store_needs_the_right_key() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let stored = stored_input_path(&dir.path().join(STORE_DIR), 2024, 1);
+        fs::create_dir_all(stored.parent().unwrap()).unwrap();
+        fs::write(
+            &stored,
+            store::encrypt(&Secret::new("other"), 2024, 1, "store")?,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            input(dir.path()).load(),
+            Err(InputError::Decrypt {
+                source: StoreError::Decrypt,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_from_crate_name() {
+        assert_eq!(1, day_from_crate_name("day-01"));
+        assert_eq!(25, day_from_crate_name("day-25"));
+    }
+}
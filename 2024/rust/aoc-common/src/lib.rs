@@ -0,0 +1,17 @@
+// lets the expansion of `#[memoize]` name this crate from inside it too
+extern crate self as aoc_common;
+
+pub mod alloc;
+pub mod cycle;
+pub mod input;
+pub mod math;
+pub mod memo;
+pub mod search;
+pub mod solution;
+pub mod store;
+
+pub use aoc_macros::memoize;
+pub use solution::{Part, Solution};
+
+/// Year solved by the days in this workspace.
+pub const YEAR: u16 = 2024;
@@ -0,0 +1,291 @@
+//! Exact integer math the puzzles keep coming back to: divisibility, modular
+//! arithmetic and linear equations, without any floating point rounding.
+use std::fmt::{self, Display};
+
+/// Greatest common divisor, never negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    gcd_i128(a.into(), b.into()) as i64
+}
+
+/// Least common multiple, never negative, `0` if either is `0`.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Extended Euclid, `(g, x, y)` with `a * x + b * y = g` and `g` the
+/// [`gcd`] of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Chinese Remainder Theorem, the `(residue, modulus)` of the numbers that
+/// leave every `(residue, modulus)` of `congruences`. The moduli need not be
+/// coprime, there is no solution if the congruences contradict each other.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut solution = (0i128, 1i128);
+    for &(residue, modulus) in congruences {
+        let (r1, m1) = solution;
+        let (r2, m2) = (i128::from(residue), i128::from(modulus));
+        let g = gcd_i128(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+        // m1 * k ≡ r2 - r1 (mod m2), solved in the moduli divided by g
+        let m = m2 / g;
+        let (_, inverse, _) = extended_gcd((m1 / g % m) as i64, m as i64);
+        let k = ((r2 - r1) / g % m * i128::from(inverse)).rem_euclid(m);
+        let lcm = m1 * m;
+        solution = ((r1 + m1 * k).rem_euclid(lcm), lcm);
+    }
+    let (residue, modulus) = solution;
+    Some((i64::try_from(residue).ok()?, i64::try_from(modulus).ok()?))
+}
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    pub numer: i128,
+    pub denom: i128,
+}
+
+impl Ratio {
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "the denominator of a ratio should not be 0");
+        let g = gcd_i128(numer, denom) * denom.signum();
+        Self {
+            numer: numer / g,
+            denom: denom / g,
+        }
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(n: i128) -> Self {
+        Self { numer: n, denom: 1 }
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denom {
+            1 => write!(f, "{}", self.numer),
+            denom => write!(f, "{}/{denom}", self.numer),
+        }
+    }
+}
+
+/// Solves the square system `matrix * x = rhs` exactly with fraction-free
+/// (Bareiss) elimination, `None` if it has no single solution.
+pub fn solve_linear(matrix: &[Vec<i128>], rhs: &[i128]) -> Option<Vec<Ratio>> {
+    let n = rhs.len();
+    assert!(
+        matrix.len() == n && matrix.iter().all(|row| row.len() == n),
+        "the matrix should be square and as high as the right hand side"
+    );
+    let mut m: Vec<Vec<i128>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().copied().chain([b]).collect())
+        .collect();
+
+    // every division is exact, the entries stay minors of the matrix
+    let mut previous = 1;
+    for k in 0..n {
+        let pivot = (k..n).find(|&i| m[i][k] != 0)?;
+        m.swap(k, pivot);
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous;
+            }
+            m[i][k] = 0;
+        }
+        previous = m[k][k];
+    }
+
+    // back substitution scaled by the determinant, so it stays in integers
+    let det = previous;
+    let mut scaled = vec![0; n];
+    for i in (0..n).rev() {
+        let sum: i128 = (i + 1..n).map(|j| m[i][j] * scaled[j]).sum();
+        scaled[i] = (det * m[i][n] - sum) / m[i][i];
+    }
+    Some(scaled.into_iter().map(|x| Ratio::new(x, det)).collect())
+}
+
+/// Non-negative integer solutions of `a * x + b * y = c` for positive `a`
+/// and `b`, by increasing `x`, see [`non_negative_solutions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solutions {
+    /// Solution with the smallest `x`.
+    pub first: (i64, i64),
+    /// What the next solution adds to `x` and takes from `y`.
+    pub step: (i64, i64),
+    pub count: i64,
+}
+
+impl Solutions {
+    /// The `i`th solution, by increasing `x`.
+    pub fn get(&self, i: i64) -> Option<(i64, i64)> {
+        (0..self.count).contains(&i).then(|| {
+            (
+                self.first.0 + i * self.step.0,
+                self.first.1 - i * self.step.1,
+            )
+        })
+    }
+
+    pub fn last(&self) -> Option<(i64, i64)> {
+        self.get(self.count - 1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (0..self.count).filter_map(|i| self.get(i))
+    }
+}
+
+/// Every non-negative integer `(x, y)` with `a * x + b * y = c`, for
+/// positive `a` and `b`. There can be many, so they are only enumerated on
+/// demand.
+pub fn non_negative_solutions(a: i64, b: i64, c: i64) -> Solutions {
+    assert!(a > 0 && b > 0, "the coefficients should be positive");
+    let none = Solutions {
+        first: (0, 0),
+        step: (b, a),
+        count: 0,
+    };
+    let (g, x, _) = extended_gcd(a, b);
+    if c < 0 || c % g != 0 {
+        return none;
+    }
+    let step = (b / g, a / g);
+    // smallest non-negative x, the largest y then follows from it
+    let x = (i128::from(x) * i128::from(c / g)).rem_euclid(step.0.into()) as i64;
+    let y = (c - a * x) / b;
+    if y < 0 {
+        return Solutions { step, ..none };
+    }
+    Solutions {
+        first: (x, y),
+        step,
+        count: y / step.1 + 1,
+    }
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(36, lcm(-12, 18));
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(6, 9));
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1, 6), (2, 4)]));
+        // the first machine of day 13
+        assert_eq!(
+            Some(vec![Ratio::from(80), Ratio::from(40)]),
+            solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400])
+        );
+        assert_eq!(None, solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]));
+        assert_eq!("-1/2", Ratio::new(2, -4).to_string());
+        let solutions = non_negative_solutions(4, 6, 20);
+        assert_eq!(vec![(2, 2), (5, 0)], solutions.iter().collect::<Vec<_>>());
+    }
+
+    proptest! {
+        #[test]
+        fn test_extended_gcd(a in -10_000i64..10_000, b in -10_000i64..10_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(gcd(a, b), g);
+            prop_assert_eq!(g, a * x + b * y);
+            if g != 0 {
+                prop_assert_eq!((0, 0), (a % g, b % g));
+                prop_assert_eq!(0, lcm(a, b) % a.max(1));
+            }
+        }
+
+        #[test]
+        fn test_mod_inverse(a in -1_000i64..1_000, m in 2i64..1_000) {
+            match mod_inverse(a, m) {
+                Some(inverse) => prop_assert_eq!(1, (a * inverse).rem_euclid(m)),
+                None => prop_assert_ne!(1, gcd(a, m)),
+            }
+        }
+
+        #[test]
+        fn test_crt(n in 0i64..1_000_000, moduli in prop::collection::vec(1i64..100, 1..5)) {
+            let congruences = moduli.iter().map(|&m| (n % m, m)).collect::<Vec<_>>();
+            let (residue, modulus) = crt(&congruences).unwrap();
+            prop_assert_eq!(moduli.iter().fold(1, |acc, &m| lcm(acc, m)), modulus);
+            prop_assert_eq!(n % modulus, residue);
+        }
+
+        #[test]
+        fn test_solve_linear(
+            (matrix, x) in (1usize..5).prop_flat_map(|n| (
+                prop::collection::vec(prop::collection::vec(-20i128..20, n), n),
+                prop::collection::vec(-50i128..50, n),
+            ))
+        ) {
+            let rhs = matrix
+                .iter()
+                .map(|row| row.iter().zip(&x).map(|(a, b)| a * b).sum())
+                .collect::<Vec<i128>>();
+            // a unique solution has to be the one the system was built from
+            if let Some(solution) = solve_linear(&matrix, &rhs) {
+                let solution = solution.iter().map(|r| r.to_integer()).collect::<Option<Vec<_>>>();
+                prop_assert_eq!(Some(x), solution);
+            }
+        }
+
+        #[test]
+        fn test_non_negative_solutions(a in 1i64..30, b in 1i64..30, c in 0i64..300) {
+            let expected = (0..=c / a)
+                .filter(|x| (c - a * x) % b == 0)
+                .map(|x| (x, (c - a * x) / b))
+                .collect::<Vec<_>>();
+            let solutions = non_negative_solutions(a, b, c);
+            prop_assert_eq!(expected.len() as i64, solutions.count);
+            prop_assert_eq!(expected, solutions.iter().collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! The caches behind `#[memoize]`, which wraps the body of a function in
+//! [`memoized`] with a thread local [`Caches`] of its own.
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    hash::Hash,
+    thread::LocalKey,
+};
+
+/// Caches of one function, one per key and value types, so every
+/// instantiation of a generic function gets its own.
+pub type Caches = RefCell<HashMap<TypeId, Box<dyn Any>>>;
+
+thread_local! {
+    static STATS: RefCell<HashMap<&'static str, Stats>> = RefCell::new(HashMap::new());
+}
+
+/// How often a memoized function found its value in the cache, see [`stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Values in the cache.
+    pub cached: usize,
+}
+
+/// Statistics of the function `name` memoized with `stats`, as its
+/// outermost call on this thread last returned. They add up over the calls
+/// of a `thread_local` cache, and only cover the last call otherwise.
+pub fn stats(name: &str) -> Option<Stats> {
+    STATS.with_borrow(|stats| stats.get(name).copied())
+}
+
+/// How a memoized function keeps its cache, set through the arguments of
+/// `#[memoize]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Name of the function in the statistics.
+    pub name: &'static str,
+    /// Keeps the cache for the lifetime of the thread, instead of dropping
+    /// it when the outermost call returns.
+    pub thread_local: bool,
+    /// Reports the hits and misses when the outermost call returns.
+    pub stats: bool,
+}
+
+#[derive(Debug)]
+struct Memo<K, V> {
+    values: HashMap<K, V>,
+    /// Calls on the stack that missed the cache.
+    depth: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            depth: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+/// The value `compute` gives for `key`, only computed if no call within
+/// the lifetime of the cache computed it before.
+pub fn memoized<K, V>(
+    caches: &'static LocalKey<Caches>,
+    options: Options,
+    key: K,
+    compute: impl FnOnce() -> V,
+) -> V
+where
+    K: Hash + Eq + 'static,
+    V: Clone + 'static,
+{
+    let hit = with_memo(caches, |memo: &mut Memo<K, V>| {
+        let hit = memo.values.get(&key).cloned();
+        match hit {
+            Some(_) => memo.hits += 1,
+            None => {
+                memo.misses += 1;
+                memo.depth += 1;
+            }
+        }
+        hit
+    });
+    if let Some(value) = hit {
+        if options.stats {
+            with_memo(caches, |memo: &mut Memo<K, V>| {
+                if memo.depth == 0 {
+                    report(options, memo);
+                }
+            });
+        }
+        return value;
+    }
+
+    // the guard leaves the cache consistent even if `compute` panics
+    let mut guard = Guard::<K, V> {
+        caches,
+        options,
+        key: Some(key),
+        value: None,
+    };
+    let value = compute();
+    guard.value = Some(value.clone());
+    value
+}
+
+struct Guard<K: Hash + Eq + 'static, V: 'static> {
+    caches: &'static LocalKey<Caches>,
+    options: Options,
+    key: Option<K>,
+    /// Left empty if the computation panicked.
+    value: Option<V>,
+}
+
+impl<K: Hash + Eq + 'static, V: 'static> Drop for Guard<K, V> {
+    fn drop(&mut self) {
+        let options = self.options;
+        let (key, value) = (self.key.take(), self.value.take());
+        with_memo(self.caches, |memo: &mut Memo<K, V>| {
+            if let (Some(key), Some(value)) = (key, value) {
+                memo.values.insert(key, value);
+            }
+            memo.depth -= 1;
+            if memo.depth > 0 {
+                return;
+            }
+            if options.stats {
+                report(options, memo);
+            }
+            if !options.thread_local {
+                *memo = Memo::default();
+            }
+        });
+    }
+}
+
+/// Logs and stores the statistics of `memo` once its outermost call returned.
+fn report<K, V>(options: Options, memo: &Memo<K, V>) {
+    let stats = Stats {
+        hits: memo.hits,
+        misses: memo.misses,
+        cached: memo.values.len(),
+    };
+    tracing::debug!(
+        function = options.name,
+        hits = stats.hits,
+        misses = stats.misses,
+        cached = stats.cached,
+        "memoized"
+    );
+    STATS.with_borrow_mut(|all| all.insert(options.name, stats));
+}
+
+fn with_memo<K, V, T>(caches: &'static LocalKey<Caches>, f: impl FnOnce(&mut Memo<K, V>) -> T) -> T
+where
+    K: 'static,
+    V: 'static,
+{
+    caches.with_borrow_mut(|caches| {
+        let memo = caches
+            .entry(TypeId::of::<Memo<K, V>>())
+            .or_insert_with(|| Box::new(Memo::<K, V>::default()))
+            .downcast_mut()
+            .expect("caches should be stored under the type id of their memo");
+        f(memo)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::{stats, Stats};
+    use crate::memoize;
+
+    thread_local! {
+        static CALLS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    #[memoize]
+    fn fibonacci(n: u64) -> u64 {
+        CALLS.set(CALLS.get() + 1);
+        if n < 2 {
+            return n;
+        }
+        fibonacci(n - 1) + fibonacci(n - 2)
+    }
+
+    #[memoize(thread_local, stats)]
+    fn ways(coins: &[u64], n: u64) -> u64 {
+        CALLS.set(CALLS.get() + 1);
+        match coins.first() {
+            _ if n == 0 => 1,
+            None => 0,
+            Some(&coin) => (0..=n / coin)
+                .map(|k| ways(&coins[1..], n - k * coin))
+                .sum(),
+        }
+    }
+
+    struct Grid(u64);
+
+    impl Grid {
+        /// Lattice paths from `(x, y)` to the far corner.
+        #[memoize(key = (x, y))]
+        fn paths(&self, x: u64, y: u64) -> u64 {
+            if x == self.0 || y == self.0 {
+                return 1;
+            }
+            self.paths(x + 1, y) + self.paths(x, y + 1)
+        }
+    }
+
+    #[memoize]
+    fn checked(text: &str, depth: usize) -> Result<usize, std::num::ParseIntError> {
+        match depth {
+            0 => Ok(text.parse()?),
+            _ => Ok(checked(text, depth - 1)? + 1),
+        }
+    }
+
+    #[test_log::test]
+    fn test_memoize() {
+        CALLS.set(0);
+        assert_eq!(12_586_269_025, fibonacci(50));
+        assert_eq!(51, CALLS.get());
+        // the cache of the first call is gone
+        assert_eq!(55, fibonacci(10));
+        assert_eq!(62, CALLS.get());
+
+        assert_eq!(137_846_528_820, Grid(20).paths(0, 0));
+        assert_eq!(6, Grid(2).paths(0, 0));
+
+        assert_eq!(Ok(7), checked("4", 3));
+        assert!(checked("x", 3).is_err());
+    }
+
+    #[test_log::test]
+    fn test_thread_local() {
+        CALLS.set(0);
+        assert_eq!(4, ways(&[1, 2, 5], 5));
+        let calls = CALLS.get();
+        let first = stats("ways").expect("ways should report its stats");
+        // the second call finds the answer left by the first
+        assert_eq!(4, ways(&[1, 2, 5], 5));
+        assert_eq!(calls, CALLS.get());
+        assert_eq!(
+            Stats {
+                hits: first.hits + 1,
+                ..first
+            },
+            stats("ways").unwrap()
+        );
+        assert_eq!(None, stats("fibonacci"));
+    }
+}
@@ -0,0 +1,321 @@
+//! Searches over graphs given as a `neighbours` closure, so a day only has to
+//! say how to get from a node to the next ones.
+//!
+//! [`bfs`] and [`dfs`] walk unweighted graphs, their closures return the next
+//! nodes. [`dijkstra`] and [`astar`] find cheapest paths, their closures
+//! return the next nodes with the cost of getting there.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Nodes reachable from a start, nearest first, see [`bfs`].
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    seen: HashSet<N>,
+    neighbours: F,
+}
+
+/// Nodes reachable from a start, each branch followed to its end before the
+/// next one, see [`dfs`].
+pub struct Dfs<N, F> {
+    stack: Vec<N>,
+    seen: HashSet<N>,
+    neighbours: F,
+}
+
+/// A cheapest path, from the start to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Walks breadth first from `start`, yielding every reachable node once with
+/// the number of steps to it.
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs {
+        seen: HashSet::from([start.clone()]),
+        queue: VecDeque::from([(start, 0)]),
+        neighbours,
+    }
+}
+
+/// Walks depth first from `start`, yielding every reachable node once.
+pub fn dfs<N, F, I>(start: N, neighbours: F) -> Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Dfs {
+        seen: HashSet::from([start.clone()]),
+        stack: vec![start],
+        neighbours,
+    }
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth) = self.queue.pop_front()?;
+        for next in (self.neighbours)(&node) {
+            if self.seen.insert(next.clone()) {
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((node, depth))
+    }
+}
+
+impl<N, F, I> Iterator for Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        for next in (self.neighbours)(&node) {
+            if self.seen.insert(next.clone()) {
+                self.stack.push(next);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// Cheapest path from `start` to a node `is_goal` accepts, costs must not be
+/// negative.
+pub fn dijkstra<N, C, F, I>(
+    start: N,
+    neighbours: F,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], exploring first the nodes `heuristic` estimates to be
+/// closest to a goal. The path is only the cheapest one if `heuristic` never
+/// overestimates the cost left.
+pub fn astar<N, C, F, I>(
+    start: N,
+    neighbours: F,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start);
+    let goal = search.run(neighbours, heuristic, is_goal)?;
+    Some(Path {
+        nodes: search.path_to(goal),
+        cost: search.costs[goal],
+    })
+}
+
+/// Cost of the cheapest path from `start` to every reachable node.
+pub fn distances<N, C, F, I>(start: N, neighbours: F) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start);
+    search.run(neighbours, |_| C::default(), |_| false);
+    search.nodes.into_iter().zip(search.costs).collect()
+}
+
+/// Number of distinct paths from `start` to the nodes `is_goal` accepts, a
+/// path ends at the first goal it reaches. The graph must not have cycles.
+pub fn count_paths<N, F, I>(start: N, mut neighbours: F, mut is_goal: impl FnMut(&N) -> bool) -> u64
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, F, I>(
+        node: &N,
+        neighbours: &mut F,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, u64>,
+    ) -> u64
+    where
+        N: Clone + Eq + Hash,
+        F: FnMut(&N) -> I,
+        I: IntoIterator<Item = N>,
+    {
+        if let Some(&paths) = memo.get(node) {
+            return paths;
+        }
+        let paths = if is_goal(node) {
+            1
+        } else {
+            let next: Vec<N> = neighbours(node).into_iter().collect();
+            next.iter()
+                .map(|next| count(next, neighbours, is_goal, memo))
+                .sum()
+        };
+        memo.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut neighbours, &mut is_goal, &mut HashMap::new())
+}
+
+/// State of a cheapest path search. Nodes are numbered in the order they are
+/// found, so the heap does not need them to be ordered.
+struct Search<N, C> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    /// Node each node is reached from on its cheapest path, the start has none.
+    parents: Vec<Option<usize>>,
+    costs: Vec<C>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    fn new(start: N) -> Self {
+        Self {
+            indices: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![start],
+            parents: vec![None],
+            costs: vec![C::default()],
+        }
+    }
+
+    /// Searches until a goal is reached, returning its index.
+    fn run<F, I>(
+        &mut self,
+        mut neighbours: F,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<usize>
+    where
+        F: FnMut(&N) -> I,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let mut heap = BinaryHeap::from([Reverse((heuristic(&self.nodes[0]), C::default(), 0))]);
+
+        while let Some(Reverse((_, cost, index))) = heap.pop() {
+            // a cheaper way there was found after this one was queued
+            if cost > self.costs[index] {
+                continue;
+            }
+            if is_goal(&self.nodes[index]) {
+                return Some(index);
+            }
+
+            for (next, step) in neighbours(&self.nodes[index]) {
+                let next_cost = cost + step;
+                let next_index = match self.indices.get(&next) {
+                    Some(&i) if self.costs[i] <= next_cost => continue,
+                    Some(&i) => {
+                        self.costs[i] = next_cost;
+                        self.parents[i] = Some(index);
+                        i
+                    }
+                    None => {
+                        let i = self.nodes.len();
+                        self.indices.insert(next.clone(), i);
+                        self.nodes.push(next);
+                        self.parents.push(Some(index));
+                        self.costs.push(next_cost);
+                        i
+                    }
+                };
+                let estimate = next_cost + heuristic(&self.nodes[next_index]);
+                heap.push(Reverse((estimate, next_cost, next_index)));
+            }
+        }
+        None
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            index = parent;
+            path.push(self.nodes[index].clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `0 -> 1 -> 3`, `0 -> 2 -> 3 -> 4`.
+    fn diamond(node: &u32) -> Vec<u32> {
+        match node {
+            0 => vec![1, 2],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_walks() {
+        assert_eq!(
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)],
+            bfs(0, diamond).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0, 2, 3, 4, 1], dfs(0, diamond).collect::<Vec<_>>());
+        assert_eq!(2, count_paths(0, diamond, |&n| n == 4));
+        assert_eq!(1, count_paths(0, diamond, |&n| n == 1));
+        assert_eq!(0, count_paths(4, diamond, |&n| n == 0));
+    }
+
+    #[test]
+    fn test_cheapest_path() {
+        // the direct edge to 3 costs more than going around
+        let edges = |node: &u32| match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        };
+        let path = Path {
+            nodes: vec![0, 1, 2, 3],
+            cost: 6,
+        };
+        assert_eq!(Some(path.clone()), dijkstra(0, edges, |&n| n == 3));
+        assert_eq!(Some(path), astar(0, edges, |&n| 3 - n, |&n| n == 3));
+        assert_eq!(None, dijkstra(3, edges, |&n| n == 0));
+        assert_eq!(
+            HashMap::from([(0, 0), (1, 1), (2, 3), (3, 6)]),
+            distances(0, edges)
+        );
+    }
+}
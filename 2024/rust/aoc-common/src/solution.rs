@@ -0,0 +1,56 @@
+//! The shape every day takes, so the runner, benches and tests can treat all
+//! days alike and the input is only parsed once for both parts.
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+pub trait Solution {
+    /// The puzzle input, parsed into what both parts work on.
+    type Parsed;
+    type Answer: Display;
+
+    fn parse(input: &str) -> miette::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Self::Answer;
+    fn part2(parsed: &Self::Parsed) -> Self::Answer;
+
+    fn part(parsed: &Self::Parsed, part: Part) -> Self::Answer {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+
+    /// Parses `input` and solves `part` of it.
+    fn solve(input: &str, part: Part) -> miette::Result<Self::Answer> {
+        Ok(Self::part(&Self::parse(input)?, part))
+    }
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part `{s}` must be either `1` or `2`")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
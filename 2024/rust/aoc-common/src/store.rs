@@ -0,0 +1,186 @@
+//! Encrypted puzzle inputs, so they can be committed without publishing them.
+//!
+//! The input of a day is stored as `inputs/<year>/day-NN.enc`, encrypted with
+//! ChaCha20-Poly1305 under a key derived with Argon2 from the passphrase in
+//! `AOC_INPUT_KEY` or the contents of the file named by `AOC_INPUT_KEYFILE`.
+//! A stored input is `MAGIC || salt || nonce || ciphertext`, and the year and
+//! day are authenticated with it so inputs cannot be swapped between days.
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+};
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Directory of the store, relative to the workspace.
+pub const STORE_DIR: &str = "inputs";
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+pub const KEYFILE_VAR: &str = "AOC_INPUT_KEYFILE";
+
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Passphrase or keyfile contents the keys of the store are derived from.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(Vec<u8>);
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum StoreError {
+    #[error("no key to decrypt the input store")]
+    #[diagnostic(
+        code(aoc::store::no_key),
+        help("set `{KEY_VAR}` to the passphrase or `{KEYFILE_VAR}` to the keyfile of the store, e.g. in `.env`")
+    )]
+    NoKey,
+    #[error("failed to read keyfile {}", path.display())]
+    #[diagnostic(code(aoc::store::keyfile))]
+    KeyFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to derive a key: {0}")]
+    #[diagnostic(code(aoc::store::kdf))]
+    Kdf(String),
+    #[error("not an encrypted input")]
+    #[diagnostic(code(aoc::store::malformed))]
+    Malformed,
+    #[error("failed to decrypt, the key is wrong or the file was tampered with")]
+    #[diagnostic(code(aoc::store::decrypt))]
+    Decrypt,
+    #[error("decrypted input is not valid utf-8")]
+    #[diagnostic(code(aoc::store::utf8))]
+    Utf8,
+}
+
+impl Secret {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Reads the secret from `AOC_INPUT_KEY`, or else from the keyfile in
+    /// `AOC_INPUT_KEYFILE`, trailing whitespace is ignored in both.
+    pub fn from_env() -> Result<Option<Self>, StoreError> {
+        if let Some(passphrase) = env::var_os(KEY_VAR).filter(|key| !key.is_empty()) {
+            return Ok(Some(Self::new(
+                passphrase.to_string_lossy().trim_end().as_bytes(),
+            )));
+        }
+        let Some(path) = env::var_os(KEYFILE_VAR).filter(|path| !path.is_empty()) else {
+            return Ok(None);
+        };
+        let path = PathBuf::from(path);
+        let mut contents =
+            fs::read(&path).map_err(|source| StoreError::KeyFile { path, source })?;
+        contents.truncate(contents.trim_ascii_end().len());
+        Ok(Some(Self(contents)))
+    }
+
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305, StoreError> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(&self.0, salt, &mut key)
+            .map_err(|e| StoreError::Kdf(e.to_string()))?;
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+pub fn stored_input_path(store_dir: &Path, year: u16, day: u8) -> PathBuf {
+    store_dir
+        .join(year.to_string())
+        .join(format!("day-{day:02}.enc"))
+}
+
+pub fn encrypt(secret: &Secret, year: u16, day: u8, input: &str) -> Result<Vec<u8>, StoreError> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).expect("should be able to get random bytes");
+    getrandom::getrandom(&mut nonce).expect("should be able to get random bytes");
+
+    let aad = associated_data(year, day);
+    let ciphertext = secret
+        .cipher(&salt)?
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: input.as_bytes(),
+                aad: aad.as_bytes(),
+            },
+        )
+        .expect("inputs should be small enough to encrypt");
+    Ok([MAGIC, &salt, &nonce, &ciphertext].concat())
+}
+
+pub fn decrypt(secret: &Secret, year: u16, day: u8, stored: &[u8]) -> Result<String, StoreError> {
+    let rest = stored.strip_prefix(MAGIC).ok_or(StoreError::Malformed)?;
+    if rest.len() < SALT_LEN + NONCE_LEN {
+        return Err(StoreError::Malformed);
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let aad = associated_data(year, day);
+    let input = secret
+        .cipher(salt)?
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: aad.as_bytes(),
+            },
+        )
+        .map_err(|_| StoreError::Decrypt)?;
+    String::from_utf8(input).map_err(|_| StoreError::Utf8)
+}
+
+fn associated_data(year: u16, day: u8) -> String {
+    format!("{year}/day-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let secret = Secret::new("correct horse battery staple");
+        let stored = encrypt(&secret, 2024, 1, "3   4\n4   3\n")?;
+
+        assert!(stored.starts_with(MAGIC));
+        assert!(!String::from_utf8_lossy(&stored).contains("3   4"));
+        assert_eq!("3   4\n4   3\n", decrypt(&secret, 2024, 1, &stored)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrong_key_or_day() -> miette::Result<()> {
+        let stored = encrypt(&Secret::new("right"), 2024, 1, "input")?;
+
+        assert!(matches!(
+            decrypt(&Secret::new("wrong"), 2024, 1, &stored),
+            Err(StoreError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(&Secret::new("right"), 2024, 2, &stored),
+            Err(StoreError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(&Secret::new("right"), 2024, 1, b"input"),
+            Err(StoreError::Malformed)
+        ));
+        Ok(())
+    }
+}
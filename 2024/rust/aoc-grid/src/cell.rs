@@ -0,0 +1,85 @@
+//! Cells that are written as a char, usually derived with
+//! `#[derive(GridCell)]` on an enum of the things a map shows.
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{Grid, GridError};
+
+pub trait GridCell: Sized {
+    /// The chars of the variants, in order, not counting an `other` one.
+    const CHARS: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+
+    /// How the cell is written, the first of its chars.
+    fn to_char(&self) -> char;
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{c}` is not one of `{expected}`")]
+#[diagnostic(code(aoc::grid::unknown_cell))]
+pub struct CellError {
+    pub c: char,
+    /// The chars cells can be, [`GridCell::CHARS`].
+    pub expected: &'static str,
+}
+
+impl<T: GridCell> Grid<T> {
+    /// Parses the lines of `input` into rows of cells, failing with the
+    /// position of the first char that is no cell.
+    pub fn parse_cells(input: &str) -> Result<Self, GridError> {
+        Self::try_parse(input, T::from_char)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::IVec2;
+
+    use super::*;
+    use crate::GridCell;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+    enum Tile {
+        #[cell('.', ' ')]
+        Empty,
+        #[cell('#')]
+        Wall,
+        #[cell(other)]
+        Antenna(char),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+    enum Floor {
+        #[cell('.')]
+        Empty,
+        #[cell('^')]
+        Guard,
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!(Some(Tile::Empty), Tile::from_char(' '));
+        assert_eq!(Some(Tile::Antenna('a')), Tile::from_char('a'));
+        assert_eq!('.', char::from(Tile::Empty));
+        assert_eq!("a", Tile::Antenna('a').to_string());
+        assert_eq!(". #", Tile::CHARS);
+
+        assert_eq!(Floor::Guard, Floor::try_from('^').unwrap());
+        let error = Floor::try_from('#').unwrap_err();
+        assert_eq!("`#` is not one of `.^`", error.to_string());
+    }
+
+    #[test]
+    fn test_parse_cells() -> miette::Result<()> {
+        let grid = Grid::<Tile>::parse_cells("#a\n. ")?;
+        assert_eq!("#a\n..", grid.to_string());
+
+        let pos = IVec2::new(0, 1);
+        assert!(matches!(
+            Grid::<Floor>::parse_cells("..\n#^"),
+            Err(GridError::Cell { c: '#', pos: p, .. }) if p == pos
+        ));
+        Ok(())
+    }
+}
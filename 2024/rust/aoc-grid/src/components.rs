@@ -0,0 +1,155 @@
+//! Labelling of the connected components of a grid, the regions of
+//! orthogonally adjacent cells that belong together, with their shape.
+use glam::IVec2;
+
+use crate::{Direction, Grid};
+
+/// Components of a grid and which one every cell belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// Index in `components` of the component of every cell.
+    pub labels: Grid<usize>,
+    /// In the order their first cell comes row by row.
+    pub components: Vec<Component>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// First cell of the component, row by row.
+    pub start: IVec2,
+    pub area: usize,
+    /// Edges of its cells that are not shared with another of its cells.
+    pub perimeter: usize,
+    /// Straight sides of its outline, holes included.
+    pub sides: usize,
+    /// Top left corner of its bounding box.
+    pub min: IVec2,
+    /// Bottom right corner of its bounding box, inclusive.
+    pub max: IVec2,
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Components of orthogonally adjacent cells that are equal.
+    pub fn components(&self) -> Components {
+        self.components_by(|a, b| a == b)
+    }
+
+    /// Components of orthogonally adjacent cells `connected` joins.
+    pub fn components_by(&self, mut connected: impl FnMut(&T, &T) -> bool) -> Components {
+        let mut labels = Grid::filled(self.width(), self.height(), usize::MAX);
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+
+        // flood fill with a stack, so large components cannot overflow it
+        for start in self.positions() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let label = components.len();
+            labels[start] = label;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                for (next, cell) in self.neighbours4(pos) {
+                    if labels[next] == usize::MAX && connected(&self[pos], cell) {
+                        labels[next] = label;
+                        stack.push(next);
+                    }
+                }
+            }
+            components.push(Component {
+                start,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                min: start,
+                max: start,
+            });
+        }
+
+        for (pos, &label) in labels.iter() {
+            let same = |offset: IVec2| labels.get(pos + offset) == Some(&label);
+            let component = &mut components[label];
+            component.area += 1;
+            component.min = component.min.min(pos);
+            component.max = component.max.max(pos);
+            for direction in Direction::ALL {
+                let side = direction.delta();
+                let next_side = direction.turn_right().delta();
+                if !same(side) {
+                    component.perimeter += 1;
+                }
+                // a polygon has as many sides as corners, every cell has
+                // one on each of its convex and concave corners
+                let convex = !same(side) && !same(next_side);
+                let concave = same(side) && same(next_side) && !same(side + next_side);
+                if convex || concave {
+                    component.sides += 1;
+                }
+            }
+        }
+
+        Components { labels, components }
+    }
+}
+
+impl Components {
+    /// Component the cell at `pos` belongs to.
+    pub fn of(&self, pos: IVec2) -> Option<&Component> {
+        self.labels.get(pos).map(|&label| &self.components[label])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_components() -> miette::Result<()> {
+        let grid = Grid::parse(
+            "AAAA
+BBCD
+BBCC
+EEEC",
+            |c| c,
+        )?;
+        let components = grid.components();
+        let shapes = components
+            .components
+            .iter()
+            .map(|c| (grid[c.start], c.area, c.perimeter, c.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4)
+            ],
+            shapes
+        );
+
+        let c = components.of(IVec2::new(3, 3)).unwrap();
+        assert_eq!((IVec2::new(2, 1), IVec2::new(3, 3)), (c.min, c.max));
+        assert_eq!(2, components.labels[IVec2::new(2, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_holes() -> miette::Result<()> {
+        let grid = Grid::parse(
+            "OOOOO
+OXOXO
+OOOOO",
+            |c| c,
+        )?;
+        let components = grid.components();
+        assert_eq!(3, components.components.len());
+        let outer = &components.components[0];
+        assert_eq!(
+            (13, 16 + 8, 4 + 8),
+            (outer.area, outer.perimeter, outer.sides)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,384 @@
+//! Headings to move around a [`Grid`](crate::Grid) with, and compact sets of
+//! them to remember which way a position was already crossed.
+use std::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use glam::IVec2;
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// One of the 4 orthogonal directions, with up towards the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the 8 directions including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Directions with a fixed number of values, so they fit a [`DirectionSet`].
+pub trait Compass: Copy + 'static {
+    /// Every value, clockwise from up.
+    const ALL: &'static [Self];
+
+    /// Position of the value in [`Compass::ALL`].
+    fn index(self) -> usize;
+}
+
+/// Set of directions stored as bits, e.g. the headings a position was
+/// visited with.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectionSet<D = Direction> {
+    bits: u8,
+    _compass: PhantomData<D>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{0}` is not a direction")]
+#[diagnostic(
+    code(aoc::grid::direction),
+    help("directions are one of `^>v<`, `URDL` or `NESW`")
+)]
+pub struct ParseDirectionError(pub String);
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        self.turn_around().turn_right()
+    }
+
+    pub const fn turn_around(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Offset of a step in this direction.
+    pub const fn delta(self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::NEG_Y,
+            Direction::Right => IVec2::X,
+            Direction::Down => IVec2::Y,
+            Direction::Left => IVec2::NEG_X,
+        }
+    }
+
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// Parses an arrow `^>v<`, a letter `URDL` or a compass point `NESW`.
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Turns an eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns an eighth of a turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Offset of a step in this direction.
+    pub const fn delta(self) -> IVec2 {
+        match self {
+            Direction8::Up => IVec2::NEG_Y,
+            Direction8::UpRight => IVec2::new(1, -1),
+            Direction8::Right => IVec2::X,
+            Direction8::DownRight => IVec2::ONE,
+            Direction8::Down => IVec2::Y,
+            Direction8::DownLeft => IVec2::new(-1, 1),
+            Direction8::Left => IVec2::NEG_X,
+            Direction8::UpLeft => IVec2::NEG_ONE,
+        }
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl Compass for Direction {
+    const ALL: &'static [Self] = &Direction::ALL;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Compass for Direction8 {
+    const ALL: &'static [Self] = &Direction8::ALL;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl From<Direction> for IVec2 {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+impl From<Direction8> for IVec2 {
+    fn from(direction: Direction8) -> Self {
+        direction.delta()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Direction::from_char(c).ok_or_else(|| ParseDirectionError(c.to_string()))
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+/// Parses a compass point, `N`, `NE`, `E`, ... or `NW`.
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction8::UpRight),
+            "SE" => Ok(Direction8::DownRight),
+            "SW" => Ok(Direction8::DownLeft),
+            "NW" => Ok(Direction8::UpLeft),
+            _ => s.parse::<Direction>().map(Direction8::from),
+        }
+    }
+}
+
+/// Shows the direction as an arrow, `^>v<`.
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+        write!(f, "{arrow}")
+    }
+}
+
+impl<D: Compass> DirectionSet<D> {
+    pub const fn new() -> Self {
+        Self {
+            bits: 0,
+            _compass: PhantomData,
+        }
+    }
+
+    /// Adds `direction`, returning whether it was not in the set yet.
+    pub fn insert(&mut self, direction: D) -> bool {
+        let bit = 1 << direction.index();
+        let added = self.bits & bit == 0;
+        self.bits |= bit;
+        added
+    }
+
+    /// Removes `direction`, returning whether it was in the set.
+    pub fn remove(&mut self, direction: D) -> bool {
+        let bit = 1 << direction.index();
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+        removed
+    }
+
+    pub fn contains(&self, direction: D) -> bool {
+        self.bits & (1 << direction.index()) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Directions in the set, clockwise from up.
+    pub fn iter(&self) -> impl Iterator<Item = D> {
+        let bits = self.bits;
+        D::ALL
+            .iter()
+            .copied()
+            .filter(move |direction| bits & (1 << direction.index()) != 0)
+    }
+}
+
+impl<D: Compass> Default for DirectionSet<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Compass + fmt::Debug> fmt::Debug for DirectionSet<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<D: Compass> FromIterator<D> for DirectionSet<D> {
+    fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<D: Compass> Extend<D> for DirectionSet<D> {
+    fn extend<I: IntoIterator<Item = D>>(&mut self, iter: I) {
+        for direction in iter {
+            self.insert(direction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Down, Direction::Up.turn_around());
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::DownLeft, Direction8::UpRight.turn_around());
+        for direction in Direction::ALL {
+            assert_eq!(
+                IVec2::ZERO,
+                direction.delta() + direction.turn_around().delta()
+            );
+            assert_eq!(direction.delta(), Direction8::from(direction).delta());
+        }
+        assert_eq!(
+            4,
+            Direction8::ALL.iter().filter(|d| d.is_diagonal()).count()
+        );
+    }
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let arrows = "^>v<".chars().map(Direction::try_from);
+        assert_eq!(
+            Direction::ALL.to_vec(),
+            arrows.collect::<Result<Vec<_>, _>>()?
+        );
+        assert_eq!(Direction::Down, "S".parse()?);
+        assert_eq!(Direction::Left, "L".parse()?);
+        assert_eq!(Direction8::DownLeft, "SW".parse()?);
+        assert!("x".parse::<Direction>().is_err());
+        assert!("^^".parse::<Direction>().is_err());
+        assert_eq!(
+            "^>v<",
+            Direction::ALL
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<String>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_set() {
+        let mut set = DirectionSet::new();
+        assert!(set.insert(Direction::Left));
+        assert!(set.insert(Direction::Up));
+        assert!(!set.insert(Direction::Left));
+        assert_eq!(2, set.len());
+        assert_eq!(
+            vec![Direction::Up, Direction::Left],
+            set.iter().collect::<Vec<_>>()
+        );
+        assert!(set.remove(Direction::Up));
+        assert!(!set.contains(Direction::Up));
+
+        let diagonals = Direction8::ALL
+            .into_iter()
+            .filter(|d| d.is_diagonal())
+            .collect::<DirectionSet<_>>();
+        assert!(diagonals.contains(Direction8::UpLeft));
+        assert!(!diagonals.contains(Direction8::Up));
+    }
+}
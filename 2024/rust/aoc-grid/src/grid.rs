@@ -0,0 +1,456 @@
+//! A dense grid of cells, the shape most puzzle inputs take.
+//!
+//! Positions are [`IVec2`]s with `x` the column and `y` the row, counted from
+//! the top left, so stepping off the grid is just a position `get` rejects.
+use std::{
+    fmt::{self, Display},
+    iter,
+    ops::{Index, IndexMut},
+};
+
+use glam::{IVec2, Vec2Swizzles};
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::{Direction, Direction8};
+
+/// Offsets to the 4 orthogonal neighbours, clockwise from up.
+pub const ADJ4: [IVec2; 4] = [
+    Direction::Up.delta(),
+    Direction::Right.delta(),
+    Direction::Down.delta(),
+    Direction::Left.delta(),
+];
+/// Offsets to the 8 neighbours including diagonals, clockwise from up.
+pub const ADJ8: [IVec2; 8] = [
+    Direction8::Up.delta(),
+    Direction8::UpRight.delta(),
+    Direction8::Right.delta(),
+    Direction8::DownRight.delta(),
+    Direction8::Down.delta(),
+    Direction8::DownLeft.delta(),
+    Direction8::Left.delta(),
+    Direction8::UpLeft.delta(),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum GridError {
+    #[error("row {row} is {len} cells wide, but the rows above are {width}")]
+    #[diagnostic(code(aoc::grid::ragged))]
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+        #[source_code]
+        src: String,
+        #[label("this row")]
+        span: SourceSpan,
+    },
+    #[error("unexpected `{c}` at column {}, row {}", pos.x, pos.y)]
+    #[diagnostic(code(aoc::grid::cell))]
+    Cell {
+        c: char,
+        pos: IVec2,
+        #[source_code]
+        src: String,
+        #[label("no cell for this")]
+        span: SourceSpan,
+    },
+    #[error("{len} cells do not make a grid {width} wide and {height} high")]
+    #[diagnostic(code(aoc::grid::shape))]
+    Shape {
+        len: usize,
+        width: usize,
+        height: usize,
+    },
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells, row by row.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::Shape {
+                len: cells.len(),
+                width,
+                height,
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(IVec2) -> T) -> Self {
+        let cells = positions(width, height).map(&mut f).collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses the lines of `input` into rows, mapping every char to a cell.
+    /// Blank lines at the end of `input` are not rows.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Like [`Grid::parse`], failing on the chars `f` has no cell for.
+    pub fn try_parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        // the errors point into the input, so lines are tracked by offset
+        let src = || input.to_string();
+        let mut offset = 0;
+        let rows = input.trim_end_matches(['\n', '\r']);
+        for (y, raw) in rows.split_inclusive('\n').enumerate() {
+            let line = raw.trim_end_matches(['\n', '\r']);
+            let start = cells.len();
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let pos = IVec2::new(x as i32, y as i32);
+                cells.push(f(c).ok_or_else(|| GridError::Cell {
+                    c,
+                    pos,
+                    src: src(),
+                    span: (offset + i, c.len_utf8()).into(),
+                })?);
+            }
+            let len = cells.len() - start;
+            match width {
+                Some(width) if width != len => {
+                    return Err(GridError::Ragged {
+                        row: y,
+                        len,
+                        width,
+                        src: src(),
+                        span: (offset, line.len()).into(),
+                    });
+                }
+                _ => width = Some(len),
+            }
+            height += 1;
+            offset += raw.len();
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Width and height, as the position just past the bottom right corner.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Cells row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        positions(self.width, self.height)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (IVec2, &mut T)> {
+        positions(self.width, self.height).zip(&mut self.cells)
+    }
+
+    /// Orthogonal neighbours of `pos` that are on the grid.
+    pub fn neighbours4(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbours(pos, ADJ4)
+    }
+
+    /// Neighbours of `pos` that are on the grid, diagonals included.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbours(pos, ADJ8)
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        pos: IVec2,
+        offsets: [IVec2; N],
+    ) -> impl Iterator<Item = (IVec2, &T)> {
+        offsets.into_iter().filter_map(move |offset| {
+            let next = pos + offset;
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    /// Cells from `from` on, moving by `step` until falling off the grid.
+    pub fn ray(&self, from: IVec2, step: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        iter::successors(Some(from), move |pos| Some(*pos + step))
+            .map_while(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// Cells of column `x` top to bottom, none if it is off the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let len = if x < self.width { self.height } else { 0 };
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(len)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals going down and right, from the one starting at the bottom
+    /// left corner to the one starting at the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (IVec2, &T)>> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let left = (0..height).rev().map(|y| IVec2::new(0, y));
+        let top = (1..width).map(|x| IVec2::new(x, 0));
+        left.chain(top).map(|start| self.ray(start, IVec2::ONE))
+    }
+
+    /// Diagonals going down and left, from the one starting at the top left
+    /// corner to the one starting at the bottom right corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (IVec2, &T)>> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let top = (0..width).map(|x| IVec2::new(x, 0));
+        let right = (1..height).map(move |y| IVec2::new(width - 1, y));
+        top.chain(right)
+            .map(|start| self.ray(start, IVec2::new(-1, 1)))
+    }
+
+    /// First position, row by row, whose cell matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// Every position, row by row, whose cell matches `predicate`.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.iter()
+            .filter_map(move |(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along the diagonal from the top left corner.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| self[pos.yx()].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height as i32;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[IVec2::new(pos.y, height - 1 - pos.x)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.width as i32;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[IVec2::new(width - 1 - pos.y, pos.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} should be on the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} should be on the {width}x{height} grid"))
+    }
+}
+
+/// One line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn positions(width: usize, height: usize) -> impl Iterator<Item = IVec2> {
+    (0..height as i32).flat_map(move |y| (0..width as i32).map(move |x| IVec2::new(x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc
+def";
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+        assert_eq!(IVec2::new(3, 2), grid.size());
+        assert_eq!(Some(&'f'), grid.get(IVec2::new(2, 1)));
+        assert_eq!(None, grid.get(IVec2::new(-1, 0)));
+        assert_eq!(None, grid.get(IVec2::new(3, 0)));
+        assert_eq!(INPUT, grid.to_string());
+
+        let grid = Grid::parse("ab\ncd\n\n", |c| c)?;
+        assert_eq!(IVec2::new(2, 2), grid.size());
+        assert_eq!("ab\ncd", grid.to_string());
+        assert!(matches!(
+            Grid::parse("ab\n\ncd", |c| c),
+            Err(GridError::Ragged { row: 1, len: 0, .. })
+        ));
+
+        assert!(matches!(
+            Grid::parse("ab\nc", |c| c),
+            Err(GridError::Ragged {
+                row: 1,
+                len: 1,
+                width: 2,
+                ..
+            })
+        ));
+        let pos = IVec2::new(1, 1);
+        assert!(matches!(
+            Grid::try_parse("12\r\n3x", |c| c.to_digit(10)),
+            Err(GridError::Cell { c: 'x', pos: p, span, .. })
+                if p == pos && span.offset() == 5
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbours() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+        let cells = |around: &mut dyn Iterator<Item = (IVec2, &char)>| {
+            around.map(|(_, c)| *c).collect::<String>()
+        };
+        assert_eq!("bd", cells(&mut grid.neighbours4(IVec2::ZERO)));
+        assert_eq!("bcfda", cells(&mut grid.neighbours8(IVec2::new(1, 1))));
+        assert_eq!("ef", cells(&mut grid.ray(IVec2::new(1, 1), IVec2::X)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lines() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+        let line = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+        assert_eq!(
+            vec!["abc", "def"],
+            grid.rows()
+                .map(|row| line(&mut row.iter()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["ad", "be", "cf"],
+            grid.columns().map(|mut c| line(&mut c)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["d", "ae", "bf", "c"],
+            grid.diagonals()
+                .map(|d| line(&mut d.map(|(_, c)| c)))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["a", "bd", "ce", "f"],
+            grid.anti_diagonals()
+                .map(|d| line(&mut d.map(|(_, c)| c)))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.column(3).count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_transform() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT, |c| c)?;
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_cw().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_ccw().to_string());
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        assert_eq!(Some(IVec2::new(1, 1)), grid.find(|&c| c == 'e'));
+        assert_eq!("ABC\nDEF", grid.map(|c| c.to_ascii_uppercase()).to_string());
+        Ok(())
+    }
+}
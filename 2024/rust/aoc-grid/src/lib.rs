@@ -0,0 +1,13 @@
+// lets the expansions of `aoc-macros` name this crate from inside it too
+extern crate self as aoc_grid;
+
+pub mod cell;
+pub mod components;
+pub mod direction;
+pub mod grid;
+
+pub use aoc_macros::GridCell;
+pub use cell::{CellError, GridCell};
+pub use components::{Component, Components};
+pub use direction::{Compass, Direction, Direction8, DirectionSet, ParseDirectionError};
+pub use grid::{Grid, GridError, ADJ4, ADJ8};
@@ -0,0 +1,144 @@
+//! Format strings of `parse_fmt!` and `#[puzzle(fmt = ...)]`, text with
+//! `{...}` placeholders for values.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, LitStr, Type};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Piece {
+    /// Text that has to be there as is.
+    Text(String),
+    /// `\n` or `\r\n`.
+    Newline,
+    /// What is between the braces of a placeholder.
+    Value(String),
+}
+
+/// Splits `fmt` into its pieces.
+pub fn pieces(fmt: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = vec![];
+    let mut text = String::new();
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let (value, rest) = chars
+                    .as_str()
+                    .split_once('}')
+                    .ok_or("unclosed `{`, use `{{` for a literal brace")?;
+                let value = value.trim();
+                if value.is_empty() {
+                    return Err("empty `{}`, placeholders need a name".to_string());
+                }
+                flush(&mut text, &mut pieces);
+                pieces.push(Piece::Value(value.to_string()));
+                chars = rest.chars();
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".to_string()),
+            '\n' => {
+                flush(&mut text, &mut pieces);
+                pieces.push(Piece::Newline);
+            }
+            '\r' if chars.as_str().starts_with('\n') => {}
+            c => text.push(c),
+        }
+    }
+    flush(&mut text, &mut pieces);
+    Ok(pieces)
+}
+
+fn flush(text: &mut String, pieces: &mut Vec<Piece>) {
+    if !text.is_empty() {
+        pieces.push(Piece::Text(std::mem::take(text)));
+    }
+}
+
+/// Statements parsing `pieces` off `input`, binding each placeholder to the
+/// ident of `values` in the same position.
+pub fn parse_pieces(pieces: &[Piece], values: &[(Ident, Type)]) -> TokenStream {
+    let mut values = values.iter();
+    pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => quote! {
+                let (input, ()) = ::aoc_parse::__private::text(input, #text)?;
+            },
+            Piece::Newline => quote! {
+                let (input, ()) = ::aoc_parse::__private::newline(input)?;
+            },
+            Piece::Value(_) => {
+                let (ident, ty) = values
+                    .next()
+                    .expect("every placeholder should have a value");
+                quote! {
+                    let (input, #ident) = <#ty as ::aoc_parse::FromPuzzle>::from_puzzle(input)?;
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn expand(fmt: &LitStr) -> syn::Result<TokenStream> {
+    let error = |message: String| syn::Error::new(fmt.span(), message);
+    let pieces = pieces(&fmt.value()).map_err(error)?;
+    let values = pieces
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Value(ty) => Some(ty),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(i, ty)| {
+            let ty = syn::parse_str::<Type>(ty)
+                .map_err(|_| error(format!("`{ty}` is not a type, like `{{i32}}`")))?;
+            Ok((format_ident!("value_{i}"), ty))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let statements = parse_pieces(&pieces, &values);
+    let (idents, types): (Vec<_>, Vec<_>) = values.into_iter().unzip();
+    let (output, value) = match (idents.as_slice(), types.as_slice()) {
+        ([ident], [ty]) => (quote!(#ty), quote!(#ident)),
+        _ => (quote!((#(#types,)*)), quote!((#(#idents,)*))),
+    };
+    Ok(quote! {{
+        fn parse_fmt(input: ::aoc_parse::Span) -> ::aoc_parse::IResult<#output> {
+            #statements
+            ::core::result::Result::Ok((input, #value))
+        }
+        parse_fmt
+    }})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pieces() {
+        use Piece::*;
+        assert_eq!(
+            Ok(vec![
+                Text("p=".to_string()),
+                Value("i32".to_string()),
+                Text(",{".to_string()),
+                Value("pos".to_string()),
+                Text("}".to_string()),
+                Newline,
+                Value("u8".to_string()),
+            ]),
+            pieces("p={i32},{{{ pos }}}\r\n{u8}")
+        );
+        assert!(pieces("p={i32").is_err());
+        assert!(pieces("p={}").is_err());
+        assert!(pieces("p=}").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! `#[derive(FromPuzzle)]`, parsing a struct from a format string naming
+//! its fields.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::format::{self, Piece};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fmt = fmt_attribute(input)?;
+    let error = |message: String| syn::Error::new(fmt.span(), message);
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "the fields need names",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`FromPuzzle` can only be derived for structs",
+            ))
+        }
+    };
+
+    let pieces = format::pieces(&fmt.value()).map_err(error)?;
+    let mut values = vec![];
+    for piece in &pieces {
+        let Piece::Value(name) = piece else {
+            continue;
+        };
+        let field = fields
+            .iter()
+            .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+            .ok_or_else(|| error(format!("no field `{name}`")))?;
+        let ident = format_ident!("field_{name}");
+        if values.iter().any(|(known, _)| *known == ident) {
+            return Err(error(format!("`{name}` is parsed twice")));
+        }
+        values.push((ident, field.ty.clone()));
+    }
+    let field_idents = fields
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .collect::<Vec<_>>();
+    if let Some(missing) = field_idents.iter().find(|ident| {
+        !values
+            .iter()
+            .any(|(known, _)| *known == format_ident!("field_{ident}"))
+    }) {
+        return Err(error(format!("`{missing}` is never parsed")));
+    }
+
+    let statements = format::parse_pieces(&pieces, &values);
+    let locals = field_idents
+        .iter()
+        .map(|ident| format_ident!("field_{ident}"));
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::aoc_parse::FromPuzzle for #name #ty_generics #where_clause {
+            fn from_puzzle(input: ::aoc_parse::Span) -> ::aoc_parse::IResult<Self> {
+                #statements
+                ::core::result::Result::Ok((input, Self { #(#field_idents: #locals),* }))
+            }
+        }
+    })
+}
+
+/// The string of `#[puzzle(fmt = "...")]`.
+fn fmt_attribute(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut fmt = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("puzzle"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fmt") {
+                fmt = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `fmt = \"...\"`"))
+            }
+        })?;
+    }
+    fmt.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[puzzle(fmt = \"...\")]` with the layout of the fields",
+        )
+    })
+}
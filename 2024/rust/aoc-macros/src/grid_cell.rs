@@ -0,0 +1,143 @@
+//! `#[derive(GridCell)]`, mapping the chars of a grid to the variants of an
+//! enum.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::ParseStream, punctuated::Punctuated, Data, DeriveInput, Fields, Ident, LitChar, Token,
+    Variant,
+};
+
+/// What a `#[cell(...)]` attribute maps to its variant.
+enum Cell {
+    /// The chars of a unit variant, the first one is how it is written.
+    Chars(Vec<LitChar>),
+    /// Any char no other variant takes, kept in the single field.
+    Other,
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`GridCell` can only be derived for enums",
+        ));
+    };
+
+    let mut from_arms = vec![];
+    let mut to_arms = vec![];
+    let mut other_arm = None;
+    let mut chars: Vec<LitChar> = vec![];
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        match cell_attribute(variant)? {
+            Cell::Chars(variant_chars) => {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        &variant.fields,
+                        "only `#[cell(other)]` variants can have a field",
+                    ));
+                }
+                for c in &variant_chars {
+                    if chars.iter().any(|known| known.value() == c.value()) {
+                        return Err(syn::Error::new_spanned(c, "this char already has a cell"));
+                    }
+                    chars.push(c.clone());
+                }
+                let first = &variant_chars[0];
+                from_arms.push(quote!(#(#variant_chars)|* => Some(Self::#ident),));
+                to_arms.push(quote!(Self::#ident => #first,));
+            }
+            Cell::Other => {
+                if !matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+                {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "`#[cell(other)]` variants keep the char in a single field",
+                    ));
+                }
+                if other_arm.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "only one variant can be `#[cell(other)]`",
+                    ));
+                }
+                other_arm = Some(quote!(c => Some(Self::#ident(c)),));
+                to_arms.push(quote!(Self::#ident(c) => *c,));
+            }
+        }
+    }
+    let other_arm = other_arm.unwrap_or_else(|| quote!(_ => None,));
+    let expected = chars.iter().map(LitChar::value).collect::<String>();
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::aoc_grid::GridCell for #name #ty_generics #where_clause {
+            const CHARS: &'static str = #expected;
+
+            fn from_char(c: char) -> ::core::option::Option<Self> {
+                match c {
+                    #(#from_arms)*
+                    #other_arm
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    #(#to_arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::aoc_grid::CellError;
+
+            fn try_from(c: char) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::aoc_grid::GridCell>::from_char(c).ok_or_else(|| ::aoc_grid::CellError {
+                    c,
+                    expected: <Self as ::aoc_grid::GridCell>::CHARS,
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for char #where_clause {
+            fn from(cell: #name #ty_generics) -> char {
+                ::aoc_grid::GridCell::to_char(&cell)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Write::write_char(f, ::aoc_grid::GridCell::to_char(self))
+            }
+        }
+    })
+}
+
+/// The `#[cell('#')]`, `#[cell('.', ' ')]` or `#[cell(other)]` of `variant`.
+fn cell_attribute(variant: &Variant) -> syn::Result<Cell> {
+    let attr = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("cell"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &variant.ident,
+                "missing `#[cell('c')]` with the char of this variant",
+            )
+        })?;
+    attr.parse_args_with(|input: ParseStream| {
+        if input.peek(Ident) {
+            let ident = input.parse::<Ident>()?;
+            if ident != "other" {
+                return Err(syn::Error::new_spanned(ident, "expected a char or `other`"));
+            }
+            return Ok(Cell::Other);
+        }
+        let chars = Punctuated::<LitChar, Token![,]>::parse_terminated(input)?;
+        if chars.is_empty() {
+            return Err(input.error("expected the chars of this variant"));
+        }
+        Ok(Cell::Chars(chars.into_iter().collect()))
+    })
+}
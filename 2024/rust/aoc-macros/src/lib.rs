@@ -0,0 +1,72 @@
+//! Macros generating the boring parts of puzzle solutions, their expansions
+//! use `aoc_parse`, `aoc_grid` or `aoc_common`, which every crate using them
+//! depends on.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn, LitStr};
+
+mod format;
+mod from_puzzle;
+mod grid_cell;
+mod memoize;
+
+/// A parser for a record laid out like `fmt`, returning the values of its
+/// `{type}` placeholders, e.g. `parse_fmt!("p={i32},{i32}")` parses `p=0,4`
+/// into `(0, 4)`.
+///
+/// Every placeholder type must implement `aoc_parse::FromPuzzle`. A single
+/// placeholder is returned as is instead of in a tuple, `{{` and `}}` are
+/// literal braces and a newline accepts `\r\n` too.
+#[proc_macro]
+pub fn parse_fmt(input: TokenStream) -> TokenStream {
+    let fmt = parse_macro_input!(input as LitStr);
+    format::expand(&fmt)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `aoc_parse::FromPuzzle` for a struct with named fields laid
+/// out like `#[puzzle(fmt = "...")]`, whose placeholders are field names,
+/// e.g. `#[puzzle(fmt = "p={pos} v={vel}")]`.
+#[proc_macro_derive(FromPuzzle, attributes(puzzle))]
+pub fn derive_from_puzzle(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_puzzle::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `aoc_grid::GridCell`, `TryFrom<char>`, `From<Self> for char`
+/// and `Display` for an enum whose variants are each marked with the chars
+/// they are written as, e.g. `#[cell('#')]` or `#[cell('.', ' ')]`.
+///
+/// A variant with a single `char` field can be `#[cell(other)]` to take
+/// every char no other variant does.
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    grid_cell::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Caches the results of a function, so a recursive solver computes every
+/// subproblem once.
+///
+/// The cache is keyed by every argument, or by the ones in `key = (a, b)`,
+/// which can be `self`, and their values must be `Hash + Eq` once owned. The
+/// return value must be `Clone`.
+///
+/// By default the cache only lives until the outermost call returns, with
+/// `thread_local` it is kept for the next calls too. `stats` reports the
+/// hits and misses at `debug` level through `tracing` when the outermost
+/// call returns.
+#[proc_macro_attribute]
+pub fn memoize(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = memoize::Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
+    let function = parse_macro_input!(item as ItemFn);
+    memoize::expand(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
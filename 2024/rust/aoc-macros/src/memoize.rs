@@ -0,0 +1,126 @@
+//! `#[memoize]`, caching the results of a function by some of its
+//! arguments.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parenthesized, punctuated::Punctuated, token, FnArg,
+    Ident, ItemFn, Pat, ReturnType, Token, Type,
+};
+
+/// What `#[memoize(...)]` was given.
+#[derive(Default)]
+pub struct Args {
+    /// The arguments the cache is keyed by, all of them if not set.
+    key: Option<Vec<Ident>>,
+    thread_local: bool,
+    stats: bool,
+}
+
+impl Args {
+    pub fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("key") {
+            let value = meta.value()?;
+            let key = if value.peek(token::Paren) {
+                let content;
+                parenthesized!(content in value);
+                Punctuated::<Ident, Token![,]>::parse_terminated_with(&content, Ident::parse_any)?
+                    .into_iter()
+                    .collect()
+            } else {
+                vec![value.call(Ident::parse_any)?]
+            };
+            self.key = Some(key);
+        } else if meta.path.is_ident("thread_local") {
+            self.thread_local = true;
+        } else if meta.path.is_ident("stats") {
+            self.stats = true;
+        } else {
+            return Err(meta.error("expected `key = (...)`, `thread_local` or `stats`"));
+        }
+        Ok(())
+    }
+}
+
+pub fn expand(args: Args, function: ItemFn) -> syn::Result<TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = &function;
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "async functions can't be memoized",
+        ));
+    }
+
+    // every named argument, with whether it is a reference
+    let mut arguments = vec![];
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => arguments.push((
+                Ident::new("self", receiver.self_token.span),
+                receiver.reference.is_some(),
+            )),
+            FnArg::Typed(typed) => {
+                match &*typed.pat {
+                    Pat::Ident(pat) => {
+                        arguments.push((pat.ident.clone(), matches!(*typed.ty, Type::Reference(_))))
+                    }
+                    pat if args.key.is_none() => return Err(syn::Error::new_spanned(
+                        pat,
+                        "only named arguments can be part of the key, choose it with `key = (...)`",
+                    )),
+                    _ => {}
+                }
+            }
+        }
+    }
+    let key = match &args.key {
+        Some(key) => key
+            .iter()
+            .map(|ident| {
+                arguments
+                    .iter()
+                    .find(|(argument, _)| argument == ident)
+                    .ok_or_else(|| syn::Error::new_spanned(ident, "no argument with this name"))
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        None => arguments.iter().collect(),
+    };
+    // the key is owned so it can outlive the call, `&str` becomes `String`
+    let key = key.into_iter().map(|(ident, is_reference)| {
+        if *is_reference {
+            quote!(::std::borrow::ToOwned::to_owned(#ident))
+        } else {
+            quote!(::std::borrow::ToOwned::to_owned(&#ident))
+        }
+    });
+
+    let output = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+    let name = sig.ident.to_string();
+    let (thread_local, stats) = (args.thread_local, args.stats);
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            ::std::thread_local! {
+                static MEMOIZE_CACHES: ::aoc_common::memo::Caches =
+                    ::core::default::Default::default();
+            }
+            ::aoc_common::memo::memoized(
+                &MEMOIZE_CACHES,
+                ::aoc_common::memo::Options {
+                    name: #name,
+                    thread_local: #thread_local,
+                    stats: #stats,
+                },
+                (#(#key,)*),
+                || -> #output #block,
+            )
+        }
+    })
+}
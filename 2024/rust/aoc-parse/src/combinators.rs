@@ -0,0 +1,290 @@
+//! Parsers for the shapes puzzle inputs keep coming in: lines of records,
+//! rows of numbers, blank line separated sections and grids of chars.
+//!
+//! They all accept `\n` and `\r\n` line endings, and the lists stop before
+//! blank lines and trailing newlines instead of failing on them.
+use std::{error::Error, str::FromStr};
+
+use aoc_grid::{Grid, GridError};
+use glam::IVec2;
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag, take_till1},
+    character::complete::{digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{eof, map_res, opt, peek, recognize, value},
+    sequence::{pair, separated_pair, tuple},
+    Parser, Slice,
+};
+use nom_supreme::{
+    error::{BaseErrorKind, GenericErrorTree, StackContext},
+    multi::collect_separated_terminated,
+    ParserExt,
+};
+
+use crate::{ErrorTree, IResult, Span};
+
+/// An empty line, ending the line before it too, so it separates sections.
+pub fn blank_line(input: Span) -> IResult<()> {
+    value((), tuple((line_ending, space0, line_ending)))(input)
+}
+
+/// Every line of the block at the start of the input, parsed with `item`.
+/// The block ends at a blank line or the end of the input, which are left
+/// for the next parser.
+///
+/// Unlike `separated_list1`, a line that `item` fails on fails the whole parse
+/// there, instead of ending the list before it.
+pub fn lines<'a, T>(
+    item: impl Parser<Span<'a>, T, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, Vec<T>, ErrorTree<'a>> {
+    collect_separated_terminated(item, line_ending, end_of_block)
+}
+
+/// Items on the rest of the line, split by `separator`, the line ending is
+/// left for the next parser.
+pub fn row<'a, T, S>(
+    item: impl Parser<Span<'a>, T, ErrorTree<'a>>,
+    separator: impl Parser<Span<'a>, S, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, Vec<T>, ErrorTree<'a>> {
+    collect_separated_terminated(item, separator, peek(alt((line_ending, eof))))
+}
+
+/// Two sections of the input split by a blank line, like rules followed by
+/// updates.
+pub fn sections<'a, A, B>(
+    first: impl Parser<Span<'a>, A, ErrorTree<'a>>,
+    second: impl Parser<Span<'a>, B, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, (A, B), ErrorTree<'a>> {
+    separated_pair(first, blank_line, second)
+}
+
+/// Blocks split by blank lines up to the end of the input, like the machines
+/// of day 13 that take three lines each.
+pub fn blocks<'a, T>(
+    item: impl Parser<Span<'a>, T, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, Vec<T>, ErrorTree<'a>> {
+    collect_separated_terminated(item, blank_line, peek(pair(multispace0, eof)))
+}
+
+/// A number without a sign.
+pub fn unsigned<'a, T>() -> impl Parser<Span<'a>, T, ErrorTree<'a>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    number(digit1)
+}
+
+/// A number with an optional `+` or `-` sign.
+pub fn signed<'a, T>() -> impl Parser<Span<'a>, T, ErrorTree<'a>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    number(recognize(pair(opt(one_of("+-")), digit1)))
+}
+
+/// One line per number, like a list of depths.
+pub fn number_lines<'a, T>() -> impl Parser<Span<'a>, Vec<T>, ErrorTree<'a>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    lines(signed())
+}
+
+/// One line of space separated numbers per row, like the reports of day 2.
+pub fn number_rows<'a, T>() -> impl Parser<Span<'a>, Vec<Vec<T>>, ErrorTree<'a>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    lines(row(signed(), space1))
+}
+
+/// Two signed numbers split by `separator`, as a vector with `x` first.
+pub fn vec2<'a, V, N>(separator: &'static str) -> impl Parser<Span<'a>, V, ErrorTree<'a>>
+where
+    V: From<[N; 2]>,
+    N: FromStr,
+    N::Err: Error + Send + Sync + 'static,
+{
+    separated_pair(signed(), tag(separator), signed()).map(|(x, y)| V::from([x, y]))
+}
+
+/// Like [`vec2`], for the usual [`IVec2`] position, e.g. `ivec2(",")` for
+/// `3,-4`.
+pub fn ivec2<'a>(separator: &'static str) -> impl Parser<Span<'a>, IVec2, ErrorTree<'a>> {
+    vec2(separator)
+}
+
+/// A `key`, `separator` and `value` record like `Button A: X+94, Y+34`.
+///
+/// The key is the text up to the first char of `separator`, on the same
+/// line.
+pub fn key_value<'a, V>(
+    separator: &'static str,
+    value: impl Parser<Span<'a>, V, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, (&'a str, V), ErrorTree<'a>> {
+    let first = separator
+        .chars()
+        .next()
+        .expect("the separator should not be empty");
+    let key = take_till1(move |c| c == first || c == '\n' || c == '\r');
+    separated_pair(
+        key.map(|key: Span<'a>| *key.fragment()),
+        tag(separator),
+        value,
+    )
+}
+
+/// The block at the start of the input as a [`Grid`], mapping every char to
+/// a cell with `f`, see [`Grid::try_parse`].
+pub fn grid<'a, T>(
+    mut f: impl FnMut(char) -> Option<T>,
+) -> impl Parser<Span<'a>, Grid<T>, ErrorTree<'a>> {
+    move |input: Span<'a>| {
+        let (rest, block) = recognize(lines(is_not("\r\n"))).parse(input)?;
+        match Grid::try_parse(block.fragment(), &mut f) {
+            Ok(grid) => Ok((rest, grid)),
+            Err(error) => Err(nom::Err::Error(grid_error(block, error))),
+        }
+    }
+}
+
+fn end_of_block(input: Span) -> IResult<()> {
+    peek(alt((blank_line, value((), pair(multispace0, eof)))))(input)
+}
+
+fn number<'a, T>(
+    digits: impl Parser<Span<'a>, Span<'a>, ErrorTree<'a>>,
+) -> impl Parser<Span<'a>, T, ErrorTree<'a>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    map_res(digits, |digits: Span<'a>| digits.fragment().parse::<T>()).context("a number")
+}
+
+/// Points the error of parsing `block` as a grid at the cell or row it is
+/// about.
+fn grid_error(block: Span, error: GridError) -> ErrorTree {
+    let (offset, expected) = match &error {
+        GridError::Cell { span, .. } => (span.offset(), "a grid cell"),
+        GridError::Ragged { span, .. } => (span.offset(), "a row as wide as the ones above"),
+        GridError::Shape { .. } => (0, "a grid"),
+    };
+    let location = block.slice(offset..);
+    GenericErrorTree::Stack {
+        base: Box::new(GenericErrorTree::Base {
+            location,
+            kind: BaseErrorKind::External(Box::new(error)),
+        }),
+        contexts: vec![(location, StackContext::Context(expected))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::I64Vec2;
+    use nom::{character::complete::anychar, sequence::preceded};
+
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_lines() -> miette::Result<()> {
+        let rows = |input| parse(input, number_rows::<u32>());
+        assert_eq!(vec![vec![1, 2], vec![3]], rows("1 2\r\n3\n\n")?);
+        let error = rows("1 2\n3 x\n").unwrap_err();
+        assert_eq!(
+            (2, 3, "a number"),
+            (error.line, error.column, &*error.expected)
+        );
+
+        assert_eq!(vec![-1, 2], parse("-1\n+2", number_lines::<i8>())?);
+        let error = parse("1\n300", number_lines::<i8>()).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sections() -> miette::Result<()> {
+        let input = "47|53\r\n97|13\r\n\r\n75,47\r\n97\r\n";
+        let rule = separated_pair(unsigned::<u32>(), tag("|"), unsigned());
+        let (rules, updates) = parse(
+            input,
+            sections(lines(rule), lines(row(unsigned::<u32>(), tag(",")))),
+        )?;
+        assert_eq!(vec![(47, 53), (97, 13)], rules);
+        assert_eq!(vec![vec![75, 47], vec![97]], updates);
+
+        let input =
+            "Button A: X+94, Y+34\nPrize: X=8400, Y=-5\n\nButton A: X+1, Y+2\nPrize: X=3, Y=4\n";
+        let xy = |sep| {
+            separated_pair(
+                preceded(pair(tag("X"), tag(sep)), signed()),
+                tag(", "),
+                preceded(pair(tag("Y"), tag(sep)), signed()),
+            )
+            .map(|(x, y)| I64Vec2::new(x, y))
+        };
+        let machine = separated_pair(
+            key_value(": ", xy("+")),
+            line_ending,
+            key_value(": ", xy("=")),
+        );
+        let machines = parse(input, blocks(machine))?;
+        assert_eq!(2, machines.len());
+        assert_eq!(
+            (
+                ("Button A", I64Vec2::new(94, 34)),
+                ("Prize", I64Vec2::new(8400, -5))
+            ),
+            machines[0]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid() -> miette::Result<()> {
+        let input = "#.@\r\n..#\r\n\r\n<^\r\nv>\r\n";
+        let (map, moves) = parse(input, sections(grid(Some), lines(row(anychar, tag("")))))?;
+        assert_eq!(
+            (IVec2::new(3, 2), Some(&'@')),
+            (map.size(), map.get(IVec2::new(2, 0)))
+        );
+        assert_eq!(vec![vec!['<', '^'], vec!['v', '>']], moves);
+
+        let digits = |input| parse(input, grid(|c| c.to_digit(10)));
+        let error = digits("012\n3x5\n").unwrap_err();
+        assert_eq!(
+            (2, 2, "a grid cell"),
+            (error.line, error.column, &*error.expected)
+        );
+        let error = digits("012\n34\n").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        Ok(())
+    }
+
+    #[test]
+    fn test_records() -> miette::Result<()> {
+        let robot = separated_pair(
+            preceded(tag("p="), ivec2(",")),
+            space1,
+            preceded(tag("v="), ivec2(",")),
+        );
+        assert_eq!(
+            vec![(IVec2::new(0, 4), IVec2::new(3, -3))],
+            parse("p=0,4 v=3,-3\n", lines(robot))?
+        );
+        assert_eq!(
+            vec![("190", vec![10, 19])],
+            parse(
+                "190: 10 19",
+                lines(key_value(": ", row(unsigned::<u64>(), space1)))
+            )?
+        );
+        Ok(())
+    }
+}
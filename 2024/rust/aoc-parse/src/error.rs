@@ -0,0 +1,109 @@
+//! The error every parse of a puzzle input fails with, pointing at the
+//! character where the input stopped making sense.
+use std::cmp::Ordering;
+
+use miette::{Diagnostic, SourceSpan};
+use nom_supreme::error::{BaseErrorKind, Expectation, GenericErrorTree, StackContext};
+use thiserror::Error;
+
+use crate::parser::ErrorTree;
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("expected {expected} at line {line}, column {column}")]
+#[diagnostic(code(aoc::parse::expected))]
+pub struct ParseError {
+    /// What would have been valid where the input is wrong.
+    pub expected: String,
+    /// Counted from 1.
+    pub line: usize,
+    /// Counted in chars from 1.
+    pub column: usize,
+    #[source_code]
+    src: String,
+    #[label("expected {expected}")]
+    span: SourceSpan,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `input`, which is copied so the error can
+    /// show the lines around it.
+    pub fn at(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let len = input[offset..].chars().next().map_or(0, char::len_utf8);
+        Self {
+            expected: expected.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            src: input.to_string(),
+            span: (offset, len).into(),
+        }
+    }
+
+    /// Byte offset of the unexpected character in the input.
+    pub fn offset(&self) -> usize {
+        self.span.offset()
+    }
+
+    /// Error at the furthest point any branch of `tree` got to, listing what
+    /// each branch that got there expected.
+    pub(crate) fn from_tree(input: &str, tree: &ErrorTree<'_>) -> Self {
+        let (offset, mut expected) = furthest(tree);
+        expected.dedup();
+        Self::at(input, offset, expected.join(" or "))
+    }
+}
+
+fn furthest(tree: &ErrorTree<'_>) -> (usize, Vec<String>) {
+    match tree {
+        GenericErrorTree::Base { location, kind } => {
+            (location.location_offset(), vec![describe(kind)])
+        }
+        GenericErrorTree::Stack { base, contexts } => {
+            let (offset, expected) = furthest(base);
+            // the innermost context names what was being parsed best
+            match contexts.first() {
+                Some((_, StackContext::Context(context))) => (offset, vec![context.to_string()]),
+                _ => (offset, expected),
+            }
+        }
+        GenericErrorTree::Alt(trees) => trees
+            .iter()
+            .map(furthest)
+            .reduce(|(a, mut a_expected), (b, b_expected)| match a.cmp(&b) {
+                Ordering::Less => (b, b_expected),
+                Ordering::Greater => (a, a_expected),
+                Ordering::Equal => {
+                    a_expected.extend(b_expected);
+                    (a, a_expected)
+                }
+            })
+            .unwrap_or((0, vec![])),
+    }
+}
+
+fn describe<E: ToString>(kind: &BaseErrorKind<&'static str, E>) -> String {
+    match kind {
+        BaseErrorKind::Expected(Expectation::Eof) => "end of input".to_string(),
+        BaseErrorKind::Expected(Expectation::Something) => "more input".to_string(),
+        BaseErrorKind::Expected(expectation) => expectation.to_string(),
+        BaseErrorKind::Kind(kind) => format!("{kind:?}").to_lowercase(),
+        BaseErrorKind::External(error) => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let error = ParseError::at("12\n3x4\n", 4, "a digit");
+        assert_eq!((2, 2, 4), (error.line, error.column, error.offset()));
+        assert_eq!("expected a digit at line 2, column 2", error.to_string());
+
+        let error = ParseError::at("ab", 10, "more input");
+        assert_eq!((1, 3, 2), (error.line, error.column, error.offset()));
+    }
+}
@@ -0,0 +1,63 @@
+//! Pulling the numbers out of text whatever is around them, for inputs where
+//! only the numbers matter, like `Button A: X+94, Y+34`.
+use std::{any::type_name, str::FromStr};
+
+use crate::ParseError;
+
+/// Every run of digits in `text`, in order. A `-` in front is not part of
+/// the number, so `1-2` is `1` and `2`.
+pub fn unsigned_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    numbers(text, false)
+}
+
+/// Every run of digits in `text` with the `-` right in front of it, in
+/// order, so `x=-3,y=4` is `-3` and `4`.
+pub fn signed_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    numbers(text, true)
+}
+
+fn numbers<T: FromStr>(text: &str, signed: bool) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = if signed && i > 0 && bytes[i - 1] == b'-' {
+            i - 1
+        } else {
+            i
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let number = text[start..i].parse().map_err(|_| {
+            ParseError::at(
+                text,
+                start,
+                format!("a number that fits in `{}`", type_name::<T>()),
+            )
+        })?;
+        numbers.push(number);
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() -> miette::Result<()> {
+        let text = "Button A: X+94, Y-34\r\nPrize: X=8400, Y=1-2\r\n";
+        assert_eq!(vec![94, 34, 8400, 1, 2], unsigned_numbers::<u32>(text)?);
+        assert_eq!(vec![94, -34, 8400, 1, -2], signed_numbers::<i64>(text)?);
+
+        let error = signed_numbers::<i8>("1\n-200").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        assert_eq!("a number that fits in `i8`", error.expected);
+        Ok(())
+    }
+}
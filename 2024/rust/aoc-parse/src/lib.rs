@@ -0,0 +1,18 @@
+// lets the expansions of `aoc-macros` name this crate from inside it too
+extern crate self as aoc_parse;
+
+pub mod combinators;
+pub mod error;
+pub mod extract;
+pub mod parser;
+pub mod puzzle;
+
+pub use aoc_macros::{parse_fmt, FromPuzzle};
+pub use combinators::{
+    blank_line, blocks, grid, ivec2, key_value, lines, number_lines, number_rows, row, sections,
+    signed, unsigned, vec2,
+};
+pub use error::ParseError;
+pub use extract::{signed_numbers, unsigned_numbers};
+pub use parser::{parse, ErrorTree, IResult, Span};
+pub use puzzle::{__private, FromPuzzle};
@@ -0,0 +1,63 @@
+//! Running nom parsers over input that keeps track of where it is, so
+//! failures can say where the input went wrong.
+use nom::{
+    character::complete::multispace0,
+    combinator::eof,
+    sequence::{pair, terminated},
+    Parser,
+};
+use nom_locate::LocatedSpan;
+
+use crate::ParseError;
+
+/// Input of the parsers, a `&str` that knows its offset in the whole input.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// Error the parsers build up, every branch that failed with where it did.
+pub type ErrorTree<'a> = nom_supreme::error::ErrorTree<Span<'a>>;
+
+pub type IResult<'a, T> = nom::IResult<Span<'a>, T, ErrorTree<'a>>;
+
+/// Parses the whole of `input` with `parser`, only whitespace may be left
+/// over.
+pub fn parse<'a, T>(
+    input: &'a str,
+    mut parser: impl Parser<Span<'a>, T, ErrorTree<'a>>,
+) -> Result<T, ParseError> {
+    let mut whole = terminated(|i| parser.parse(i), pair(multispace0, eof));
+    match whole(Span::new(input)) {
+        Ok((_, parsed)) => Ok(parsed),
+        Err(nom::Err::Error(tree) | nom::Err::Failure(tree)) => {
+            Err(ParseError::from_tree(input, &tree))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(input, input.len(), "more input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{self, space1},
+        multi::separated_list1,
+        sequence::preceded,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let numbers = |input| parse(input, separated_list1(space1, complete::u32));
+        assert_eq!(vec![1, 2], numbers("1 2\r\n\n")?);
+
+        // a list that stops early only says the rest was not expected
+        let error = numbers("1 2 x").unwrap_err();
+        assert_eq!((1, 5), (error.line, error.column));
+        assert_eq!("end of input", error.expected);
+
+        let error = parse("p=x", preceded(tag("p="), complete::i32)).unwrap_err();
+        assert_eq!((1, 3), (error.line, error.column));
+        assert_eq!("an ascii digit", error.expected);
+        Ok(())
+    }
+}
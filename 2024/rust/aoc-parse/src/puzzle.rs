@@ -0,0 +1,120 @@
+//! Values that know how to parse themselves, which is what the placeholders
+//! of `parse_fmt!` and `#[derive(FromPuzzle)]` are filled with.
+use glam::{I64Vec2, IVec2};
+use nom::{
+    character::complete::{anychar, line_ending},
+    combinator::value,
+    Parser,
+};
+
+use crate::{ivec2, parse, signed, vec2, IResult, ParseError, Span};
+
+pub trait FromPuzzle: Sized {
+    /// Parses one `Self` off the start of `input`.
+    fn from_puzzle(input: Span) -> IResult<Self>;
+
+    /// Parses the whole of `input` as one `Self`.
+    fn from_puzzle_str(input: &str) -> Result<Self, ParseError> {
+        parse(input, Self::from_puzzle)
+    }
+}
+
+macro_rules! impl_numbers {
+    ($($t:ty)*) => {
+        $(
+            /// A number with an optional sign.
+            impl FromPuzzle for $t {
+                fn from_puzzle(input: Span) -> IResult<Self> {
+                    signed().parse(input)
+                }
+            }
+        )*
+    };
+}
+
+impl_numbers!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Any single char.
+impl FromPuzzle for char {
+    fn from_puzzle(input: Span) -> IResult<Self> {
+        anychar(input)
+    }
+}
+
+/// `x,y`, like `3,-4`.
+impl FromPuzzle for IVec2 {
+    fn from_puzzle(input: Span) -> IResult<Self> {
+        ivec2(",").parse(input)
+    }
+}
+
+/// `x,y`, like `3,-4`.
+impl FromPuzzle for I64Vec2 {
+    fn from_puzzle(input: Span) -> IResult<Self> {
+        vec2(",").parse(input)
+    }
+}
+
+/// What the expansions of the macros call, not meant to be used directly.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub fn text<'a>(input: Span<'a>, text: &'static str) -> IResult<'a, ()> {
+        value((), nom_supreme::tag::complete::tag(text))(input)
+    }
+
+    pub fn newline(input: Span) -> IResult<()> {
+        value((), line_ending)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lines, parse_fmt, FromPuzzle};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, FromPuzzle)]
+    #[puzzle(fmt = "{name}: {{{pos}}} {score}")]
+    struct Player {
+        pos: IVec2,
+        name: char,
+        score: u32,
+    }
+
+    #[test]
+    fn test_parse_fmt() -> miette::Result<()> {
+        let parser = parse_fmt!("p={i32},{i32} v={IVec2}\n#{u8}");
+        assert_eq!(
+            (0, -4, IVec2::new(3, 3), 7),
+            parse("p=0,-4 v=3,3\r\n#7", parser)?
+        );
+        assert_eq!(vec![3, 4], parse("x=3\nx=4", lines(parse_fmt!("x={u64}")))?);
+
+        let error = parse("p=0;4 v=3,3\n#7", parser).unwrap_err();
+        assert_eq!(
+            (1, 4, "\",\""),
+            (error.line, error.column, &*error.expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_derive() -> miette::Result<()> {
+        assert_eq!(
+            Player {
+                pos: IVec2::new(1, -2),
+                name: 'a',
+                score: 30
+            },
+            Player::from_puzzle_str("a: {1,-2} 30")?
+        );
+        let error = Player::from_puzzle_str("a: {1,-2} x").unwrap_err();
+        assert_eq!(
+            (1, 11, "a number"),
+            (error.line, error.column, &*error.expected)
+        );
+        Ok(())
+    }
+}
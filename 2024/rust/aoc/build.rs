@@ -0,0 +1,46 @@
+//! Generates the day registry from the `day-NN` dependencies of this crate,
+//! so a day only has to be added to `Cargo.toml` to show up in the runner.
+//!
+//! A day is registered under the `year` of its `[package.metadata.aoc]`, or
+//! under `YEAR` if it has none.
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo::rerun-if-changed=Cargo.toml");
+
+    let manifest = fs::read_to_string("Cargo.toml").expect("should be able to read Cargo.toml");
+    let mut days = manifest
+        .lines()
+        .filter_map(|line| line.split_once('=').map(|(key, _)| key.trim()))
+        .filter_map(|key| key.strip_prefix("day-"))
+        .filter_map(|day| day.parse::<u8>().ok())
+        .collect::<Vec<_>>();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut registry = String::from("&[\n");
+    for day in days {
+        let krate = format!("day_{day:02}");
+        let year = year_of(&format!("../day-{day:02}/Cargo.toml"));
+        registry +=
+            &format!("    Day {{ year: {year}, day: {day}, process: process::<{krate}::Day> }},\n");
+    }
+    registry += "]\n";
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("cargo should set OUT_DIR"));
+    fs::write(out.join("registry.rs"), registry).expect("should be able to write the registry");
+}
+
+/// The `year` in the `[package.metadata.aoc]` of the manifest at `path`.
+fn year_of(path: &str) -> String {
+    println!("cargo::rerun-if-changed={path}");
+    let manifest = fs::read_to_string(path).unwrap_or_default();
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[package.metadata.aoc]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "year")
+        .map_or_else(|| "YEAR".to_string(), |(_, year)| year.trim().to_string())
+}
@@ -0,0 +1,305 @@
+//! Known answers of a day, stored in `day-NN/answers.txt` as
+//!
+//! ```text
+//! part1: 11
+//! part2: sha256:<salt>:<hash>
+//! ```
+//!
+//! where an answer is either kept in plaintext or as a salted hash, so the
+//! file can be committed without publishing the real answers.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1, take_while1},
+    character::complete::{multispace0, space0},
+    combinator::{all_consuming, value},
+    multi::many0,
+    sequence::{pair, preceded, separated_pair, terminated},
+    IResult, Parser,
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::registry::Part;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Plain(String),
+    Hashed { salt: String, hash: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch,
+    Unknown,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum AnswersError {
+    #[error("failed to read answers {}", path.display())]
+    #[diagnostic(code(aoc::answers::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write answers {}", path.display())]
+    #[diagnostic(code(aoc::answers::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("malformed answers file {}", path.display())]
+    #[diagnostic(
+        code(aoc::answers::parse),
+        help("every line must look like `part1: <answer>` or `part1: sha256:<salt>:<hash>`")
+    )]
+    Parse { path: PathBuf },
+}
+
+impl Answer {
+    /// Hashes `answer` with a fresh random salt.
+    pub fn hashed(answer: &str) -> Self {
+        let mut salt = [0u8; 16];
+        getrandom::getrandom(&mut salt).expect("should be able to get random bytes");
+        let salt = to_hex(&salt);
+        let hash = hash(&salt, answer);
+        Answer::Hashed { salt, hash }
+    }
+
+    pub fn matches(&self, answer: &str) -> bool {
+        match self {
+            Answer::Plain(expected) => expected == answer,
+            Answer::Hashed {
+                salt,
+                hash: expected,
+            } => *expected == hash(salt, answer),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Plain(answer) => write!(f, "{answer}"),
+            Answer::Hashed { salt, hash } => write!(f, "sha256:{salt}:{hash}"),
+        }
+    }
+}
+
+impl Answers {
+    /// Reads the answers at `path`, a missing file has no known answers.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(AnswersError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let (_, answers) = parse_answers(&text).map_err(|_| AnswersError::Parse {
+            path: path.to_path_buf(),
+        })?;
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string()).map_err(|source| AnswersError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    /// Stores `answer` for `part` unless an answer is already known.
+    pub fn record(&mut self, part: Part, answer: Answer) -> bool {
+        let known = match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        };
+        if known.is_some() {
+            return false;
+        }
+        *known = Some(answer);
+        true
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(known) if known.matches(answer) => Verdict::Match,
+            Some(_) => Verdict::Mismatch,
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in Part::ALL {
+            if let Some(answer) = self.get(part) {
+                writeln!(f, "part{part}: {answer}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "match"),
+            Verdict::Mismatch => write!(f, "MISMATCH"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+fn hash(salt: &str, answer: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(answer.as_bytes());
+    to_hex(&hasher.finalize())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn parse_answers(input: &str) -> IResult<&str, Answers> {
+    let (input, lines) = all_consuming(preceded(
+        multispace0,
+        many0(terminated(parse_line, multispace0)),
+    ))(input)?;
+
+    let mut answers = Answers::default();
+    for (part, answer) in lines {
+        match part {
+            Part::One => answers.part1 = Some(answer),
+            Part::Two => answers.part2 = Some(answer),
+        }
+    }
+    Ok((input, answers))
+}
+
+fn parse_line(input: &str) -> IResult<&str, (Part, Answer)> {
+    separated_pair(
+        alt((
+            value(Part::One, tag("part1")),
+            value(Part::Two, tag("part2")),
+        )),
+        pair(tag(":"), space0),
+        alt((
+            preceded(
+                tag("sha256:"),
+                separated_pair(hex_digits, tag(":"), hex_digits),
+            )
+            .map(|(salt, hash): (&str, &str)| Answer::Hashed {
+                salt: salt.to_string(),
+                hash: hash.to_string(),
+            }),
+            take_till1(|c| c == '\r' || c == '\n')
+                .map(|answer: &str| Answer::Plain(answer.trim_end().to_string())),
+        )),
+    )(input)
+}
+
+fn hex_digits(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_hexdigit())(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let answers = Answers {
+            part1: Some(Answer::Plain("11".to_string())),
+            part2: Some(Answer::hashed("31")),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.txt");
+        answers.save(&path)?;
+
+        assert_eq!(answers, Answers::load(&path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers {
+            part1: Some(Answer::Plain("11".to_string())),
+            part2: None,
+        };
+        assert_eq!(Verdict::Match, answers.check(Part::One, "11"));
+        assert_eq!(Verdict::Mismatch, answers.check(Part::One, "12"));
+        assert_eq!(Verdict::Unknown, answers.check(Part::Two, "31"));
+    }
+
+    #[test]
+    fn test_hashed_does_not_contain_answer() {
+        let answer = Answer::hashed("65601038650482");
+        assert!(!answer.to_string().contains("65601038650482"));
+        assert!(answer.matches("65601038650482"));
+        assert!(!answer.matches("65601038650483"));
+    }
+
+    #[test]
+    fn test_record_keeps_known_answer() {
+        let mut answers = Answers::default();
+        assert!(answers.record(Part::One, Answer::Plain("11".to_string())));
+        assert!(!answers.record(Part::One, Answer::Plain("12".to_string())));
+        assert_eq!(Verdict::Match, answers.check(Part::One, "11"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "part2: sha256:00ff:abcd\r\npart1:  1,2,3\n";
+        let (_, answers) = parse_answers(input).unwrap();
+        assert_eq!(
+            Some(&Answer::Plain("1,2,3".to_string())),
+            answers.get(Part::One)
+        );
+        assert_eq!(
+            Some(&Answer::Hashed {
+                salt: "00ff".to_string(),
+                hash: "abcd".to_string()
+            }),
+            answers.get(Part::Two)
+        );
+        assert!(parse_answers("part3: 1").is_err());
+    }
+
+    #[test]
+    fn test_missing_file_has_no_answers() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            Answers::default(),
+            Answers::load(&dir.path().join("answers.txt"))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,600 @@
+//! Results of the divan benches of every day, stored in `benchmarks.csv` as
+//!
+//! ```text
+//! year,day,bench,fastest_ns,slowest_ns,median_ns,mean_ns,samples,iters,allocs,alloc_bytes,alloc_peak
+//! 2024,1,part1,42120,90990,42470,43300,100,100,2,48,48
+//! ```
+//!
+//! so runs can be compared against a saved baseline and rendered into the
+//! markdown summary in `benchmarks.md`.
+//!
+//! The allocation columns are only filled in by benches built with the
+//! `count-allocs` feature, and left empty otherwise.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+};
+
+use aoc_common::alloc::Allocs;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::registry::Day;
+
+pub const RESULTS_FILE: &str = "benchmarks.csv";
+pub const SUMMARY_FILE: &str = "benchmarks.md";
+pub const BASELINE_FILE: &str = "benchmarks.baseline.csv";
+
+const HEADER: &str =
+    "year,day,bench,fastest_ns,slowest_ns,median_ns,mean_ns,samples,iters,allocs,alloc_bytes,alloc_peak";
+/// Header of results saved before allocations were stored, still read as a
+/// baseline.
+const TIMINGS_HEADER: &str = "year,day,bench,fastest_ns,slowest_ns,median_ns,mean_ns,samples,iters";
+
+/// Timings of one bench of a day, in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    /// Name of the bench, `parse`, `part1` or `part2`.
+    pub bench: String,
+    pub fastest: f64,
+    pub slowest: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub samples: u32,
+    pub iters: u64,
+    /// What one run of the bench allocates, when counted.
+    pub allocs: Option<Allocs>,
+}
+
+/// Measurements sorted by year, day and bench.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Results {
+    pub measurements: Vec<Measurement>,
+}
+
+/// Median of a bench in a run against the one in the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub bench: String,
+    /// Missing when the bench is new since the baseline.
+    pub baseline: Option<f64>,
+    pub current: f64,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum BenchError {
+    #[error("failed to read bench results {}", path.display())]
+    #[diagnostic(code(aoc::bench::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::bench::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("malformed bench results {} on line {line}", path.display())]
+    #[diagnostic(code(aoc::bench::parse), help("the file must start with `{HEADER}`"))]
+    Parse { path: PathBuf, line: usize },
+    #[error("failed to run cargo")]
+    #[diagnostic(code(aoc::bench::cargo))]
+    Cargo(#[source] io::Error),
+    #[error("benches of {name} failed with {status}")]
+    #[diagnostic(code(aoc::bench::failed))]
+    Failed { name: String, status: ExitStatus },
+}
+
+impl Results {
+    /// Collects the tables divan prints for benches named like `day_01_bench`,
+    /// ignoring everything else in `output`, like the output of cargo test.
+    ///
+    /// The allocations the benches report with [`aoc_common::alloc::report`]
+    /// are added to the measurements of the same steps.
+    pub fn from_divan(year: u16, output: &str) -> Self {
+        let mut results = Results::default();
+        let mut allocs = vec![];
+        let mut day = None;
+        for line in output.lines() {
+            if let Some(report) = parse_allocs(line) {
+                allocs.push(report);
+            } else if line.contains("fastest") {
+                day = line
+                    .split_whitespace()
+                    .next()
+                    .and_then(|name| name.strip_prefix("day_"))
+                    .and_then(|name| name.strip_suffix("_bench"))
+                    .and_then(|day| day.parse().ok());
+            } else if let Some(day) = day {
+                if let Some(measurement) = parse_row(year, day, line) {
+                    results.insert(measurement);
+                }
+            }
+        }
+        for (day, bench, report) in allocs {
+            if let Some(m) = results
+                .measurements
+                .iter_mut()
+                .find(|m| m.year == year && m.day == day && m.bench == bench)
+            {
+                m.allocs = Some(report);
+            }
+        }
+        results
+    }
+
+    pub fn parse(path: &Path, text: &str) -> Result<Self, BenchError> {
+        let parse_err = |line| BenchError::Parse {
+            path: path.to_path_buf(),
+            line,
+        };
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        match lines.next() {
+            Some((_, header)) if [HEADER, TIMINGS_HEADER].contains(&header.trim()) => {}
+            None => return Ok(Results::default()),
+            Some((n, _)) => return Err(parse_err(n + 1)),
+        }
+
+        let mut results = Results::default();
+        for (n, line) in lines {
+            let measurement = parse_record(line).ok_or_else(|| parse_err(n + 1))?;
+            results.insert(measurement);
+        }
+        Ok(results)
+    }
+
+    /// Results stored at `path`, none if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, BenchError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(path, &text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Results::default()),
+            Err(source) => Err(BenchError::Read {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        write(path, &self.to_csv())
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{HEADER}\n");
+        for m in &self.measurements {
+            let allocs = m.allocs.map_or(",,".to_string(), |a| {
+                format!("{},{},{}", a.count, a.bytes, a.peak)
+            });
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{allocs}",
+                m.year, m.day, m.bench, m.fastest, m.slowest, m.median, m.mean, m.samples, m.iters
+            )
+            .expect("writing to a string should not fail");
+        }
+        csv
+    }
+
+    /// Table of the median and mean of every bench, and its allocations if
+    /// they were counted.
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "| year | day | bench | median | mean | samples | allocs |\n\
+             | --- | --- | --- | ---: | ---: | ---: | ---: |\n",
+        );
+        for m in &self.measurements {
+            writeln!(
+                table,
+                "| {} | day-{:02} | {} | {} | {} | {} | {} |",
+                m.year,
+                m.day,
+                m.bench,
+                format_ns(m.median),
+                format_ns(m.mean),
+                m.samples,
+                m.allocs
+                    .map_or("-".to_string(), |allocs| allocs.to_string())
+            )
+            .expect("writing to a string should not fail");
+        }
+        table
+    }
+
+    /// Adds `measurement`, replacing the one of the same bench if any.
+    pub fn insert(&mut self, measurement: Measurement) {
+        let key = |m: &Measurement| (m.year, m.day, m.bench.clone());
+        match self
+            .measurements
+            .binary_search_by_key(&key(&measurement), key)
+        {
+            Ok(index) => self.measurements[index] = measurement,
+            Err(index) => self.measurements.insert(index, measurement),
+        }
+    }
+
+    /// Adds every measurement of `other`, replacing the ones of the same benches.
+    pub fn merge(&mut self, other: Results) {
+        for measurement in other.measurements {
+            self.insert(measurement);
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u8, bench: &str) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|m| m.year == year && m.day == day && m.bench == bench)
+    }
+}
+
+impl Comparison {
+    /// Relative change of the median, `0.1` being 10% slower than the baseline.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline
+            .filter(|&baseline| baseline > 0.0)
+            .map(|baseline| self.current / baseline - 1.0)
+    }
+
+    /// Whether the bench got slower by more than `threshold`, e.g. `0.1`.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compares every bench of `current` against the same bench in `baseline`.
+pub fn compare(baseline: &Results, current: &Results) -> Vec<Comparison> {
+    current
+        .measurements
+        .iter()
+        .map(|m| Comparison {
+            year: m.year,
+            day: m.day,
+            bench: m.bench.clone(),
+            baseline: baseline.get(m.year, m.day, &m.bench).map(|b| b.median),
+            current: m.median,
+        })
+        .collect()
+}
+
+/// Table of `comparisons`, marking the ones slower than `threshold`.
+pub fn comparison_markdown(comparisons: &[Comparison], threshold: f64) -> String {
+    let mut table = String::from(
+        "| year | day | bench | baseline | current | change |\n\
+         | --- | --- | --- | ---: | ---: | ---: |\n",
+    );
+    for c in comparisons {
+        let baseline = c.baseline.map_or("-".to_string(), format_ns);
+        let change = match c.change() {
+            Some(change) if c.is_regression(threshold) => {
+                format!("{:+.1}% **regression**", change * 100.0)
+            }
+            Some(change) => format!("{:+.1}%", change * 100.0),
+            None => "new".to_string(),
+        };
+        writeln!(
+            table,
+            "| {} | day-{:02} | {} | {baseline} | {} | {change} |",
+            c.year,
+            c.day,
+            c.bench,
+            format_ns(c.current)
+        )
+        .expect("writing to a string should not fail");
+    }
+    table
+}
+
+/// Runs the benches of `day` with cargo and collects their results.
+pub fn run(workspace_dir: &Path, day: &Day) -> Result<Results, BenchError> {
+    let name = day.name();
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .args(["bench", "-q", "-p", &name, "--bench"])
+        .arg(format!("{name}-bench"))
+        .current_dir(workspace_dir)
+        .stderr(Stdio::inherit());
+    // the benches profile allocations too when the runner counts them
+    if cfg!(feature = "count-allocs") {
+        command.args(["--features", "count-allocs"]);
+    }
+    let output = command.output().map_err(BenchError::Cargo)?;
+    if !output.status.success() {
+        return Err(BenchError::Failed {
+            name,
+            status: output.status,
+        });
+    }
+    Ok(Results::from_divan(
+        day.year,
+        &String::from_utf8_lossy(&output.stdout),
+    ))
+}
+
+pub fn write(path: &Path, contents: &str) -> Result<(), BenchError> {
+    fs::write(path, contents).map_err(|source| BenchError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Parses a row of divan's table, like
+/// `├─ part1      42.12 µs      │ 90.99 µs      │ 42.47 µs      │ 43.3 µs       │ 100     │ 100`.
+fn parse_row(year: u16, day: u8, line: &str) -> Option<Measurement> {
+    let line = line
+        .strip_prefix("├─ ")
+        .or_else(|| line.strip_prefix("╰─ "))?;
+    let mut columns = line.split('│').map(str::trim);
+    let (bench, fastest) = columns.next()?.split_once(char::is_whitespace)?;
+    Some(Measurement {
+        year,
+        day,
+        bench: bench.to_string(),
+        fastest: parse_duration(fastest)?,
+        slowest: parse_duration(columns.next()?)?,
+        median: parse_duration(columns.next()?)?,
+        mean: parse_duration(columns.next()?)?,
+        samples: columns.next()?.parse().ok()?,
+        iters: columns.next()?.parse().ok()?,
+        allocs: None,
+    })
+}
+
+/// Parses a line of [`aoc_common::alloc::report`], like
+/// `day_01_bench allocs part1 2 48 48`, into the day, bench and allocations.
+fn parse_allocs(line: &str) -> Option<(u8, &str, Allocs)> {
+    let mut words = line.split_whitespace();
+    let day = words
+        .next()?
+        .strip_prefix("day_")?
+        .strip_suffix("_bench")?
+        .parse()
+        .ok()?;
+    if words.next()? != "allocs" {
+        return None;
+    }
+    let bench = words.next()?;
+    let allocs = Allocs {
+        count: words.next()?.parse().ok()?,
+        bytes: words.next()?.parse().ok()?,
+        peak: words.next()?.parse().ok()?,
+    };
+    words.next().is_none().then_some((day, bench, allocs))
+}
+
+/// Parses a duration as divan prints it, like `42.12 µs`, into nanoseconds.
+fn parse_duration(text: &str) -> Option<f64> {
+    let (value, unit) = text.trim().split_once(' ')?;
+    let scale = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    // picoseconds are as precise as divan gets, rounding drops float noise
+    let ns = value.parse::<f64>().ok()? * scale;
+    Some((ns * 1e3).round() / 1e3)
+}
+
+fn parse_record(line: &str) -> Option<Measurement> {
+    let mut fields = line.trim().split(',');
+    let measurement = Measurement {
+        year: fields.next()?.parse().ok()?,
+        day: fields.next()?.parse().ok()?,
+        bench: fields.next()?.to_string(),
+        fastest: fields.next()?.parse().ok()?,
+        slowest: fields.next()?.parse().ok()?,
+        median: fields.next()?.parse().ok()?,
+        mean: fields.next()?.parse().ok()?,
+        samples: fields.next()?.parse().ok()?,
+        iters: fields.next()?.parse().ok()?,
+        allocs: parse_record_allocs(&mut fields)?,
+    };
+    fields.next().is_none().then_some(measurement)
+}
+
+/// The allocation columns of a record, `None` inside when they are empty or
+/// missing like in results saved before they were stored.
+fn parse_record_allocs<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Option<Option<Allocs>> {
+    let Some(count) = fields.next() else {
+        return Some(None);
+    };
+    let (bytes, peak) = (fields.next()?, fields.next()?);
+    if [count, bytes, peak].iter().all(|field| field.is_empty()) {
+        return Some(None);
+    }
+    Some(Some(Allocs {
+        count: count.parse().ok()?,
+        bytes: bytes.parse().ok()?,
+        peak: peak.parse().ok()?,
+    }))
+}
+
+/// Formats nanoseconds with four significant digits, like divan does.
+fn format_ns(ns: f64) -> String {
+    let (value, unit) = match ns {
+        ns if ns < 1e3 => (ns, "ns"),
+        ns if ns < 1e6 => (ns / 1e3, "µs"),
+        ns if ns < 1e9 => (ns / 1e6, "ms"),
+        ns => (ns / 1e9, "s"),
+    };
+    let decimals = 3usize.saturating_sub(value.abs().log10().floor().max(0.0) as usize);
+    let value = format!("{value:.decimals$}");
+    let value = if value.contains('.') {
+        value.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &value
+    };
+    format!("{value} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIVAN: &str = include_str!("../fixtures/divan-output.txt");
+
+    #[test]
+    fn test_from_divan() {
+        let results = Results::from_divan(2024, DIVAN);
+        assert_eq!(5, results.measurements.len());
+        assert_eq!(
+            Some(&Measurement {
+                year: 2024,
+                day: 1,
+                bench: "part1".to_string(),
+                fastest: 42120.0,
+                slowest: 90990.0,
+                median: 42470.0,
+                mean: 43300.0,
+                samples: 100,
+                iters: 100,
+                allocs: None,
+            }),
+            results.get(2024, 1, "part1")
+        );
+        let parse = results.get(2024, 2, "parse").unwrap();
+        assert_eq!(244.6, parse.median);
+        assert_eq!(1600, parse.iters);
+        assert_eq!(1.22e9, results.get(2024, 6, "part2").unwrap().median);
+    }
+
+    #[test]
+    fn test_from_divan_with_allocs() {
+        let output = "\
+day_01_bench allocs parse 1 16 16
+day_01_bench allocs part1 2 48 32
+day_01_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+╰─ part1      117.4 ns      │ 125.6 ns      │ 121.1 ns      │ 121 ns        │ 100     │ 3200
+              alloc:        │               │               │               │         │
+                2           │ 2             │ 2             │ 2             │         │
+                48 B        │ 48 B          │ 48 B          │ 48 B          │         │
+";
+        let results = Results::from_divan(2024, output);
+        assert_eq!(1, results.measurements.len());
+        let part1 = results.get(2024, 1, "part1").unwrap();
+        assert_eq!(121.1, part1.median);
+        assert_eq!(
+            Some(Allocs {
+                count: 2,
+                bytes: 48,
+                peak: 32
+            }),
+            part1.allocs
+        );
+    }
+
+    #[test]
+    fn test_csv_round_trip() -> miette::Result<()> {
+        let results = Results::from_divan(2024, DIVAN);
+        let csv = results.to_csv();
+        assert!(csv.starts_with(&format!(
+            "{HEADER}\n2024,1,part1,42120,90990,42470,43300,100,100,,,\n"
+        )));
+        assert_eq!(results, Results::parse(Path::new(RESULTS_FILE), &csv)?);
+
+        let mut counted = results.clone();
+        counted.measurements[0].allocs = Some(Allocs {
+            count: 2,
+            bytes: 48,
+            peak: 32,
+        });
+        let csv = counted.to_csv();
+        assert!(csv.contains("\n2024,1,part1,42120,90990,42470,43300,100,100,2,48,32\n"));
+        assert_eq!(counted, Results::parse(Path::new(RESULTS_FILE), &csv)?);
+
+        let timings_only =
+            format!("{TIMINGS_HEADER}\n2024,1,part1,42120,90990,42470,43300,100,100\n");
+        assert_eq!(
+            results.get(2024, 1, "part1"),
+            Results::parse(Path::new(BASELINE_FILE), &timings_only)?.get(2024, 1, "part1")
+        );
+        assert!(matches!(
+            Results::parse(Path::new(RESULTS_FILE), "2024,1,part1"),
+            Err(BenchError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            Results::parse(
+                Path::new(RESULTS_FILE),
+                &format!("{HEADER}\n2024,1,part1,1,2\n")
+            ),
+            Err(BenchError::Parse { line: 2, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut results = Results::from_divan(2024, DIVAN);
+        let mut rerun = Results::default();
+        let mut part1 = results.get(2024, 1, "part1").unwrap().clone();
+        part1.median = 1.0;
+        rerun.insert(part1);
+
+        results.merge(rerun);
+        assert_eq!(5, results.measurements.len());
+        assert_eq!(1.0, results.get(2024, 1, "part1").unwrap().median);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Results::from_divan(2024, DIVAN);
+        let mut current = baseline.clone();
+        current.measurements[0].median *= 1.5;
+        current.measurements[1].median *= 1.05;
+        current.measurements.retain(|m| m.bench != "parse");
+        let mut new = current.measurements[0].clone();
+        new.day = 3;
+        current.insert(new);
+
+        let comparisons = compare(&baseline, &current);
+        let regressions = comparisons
+            .iter()
+            .filter(|c| c.is_regression(0.1))
+            .map(|c| (c.day, c.bench.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, "part1")], regressions);
+        assert_eq!(
+            None,
+            comparisons.iter().find(|c| c.day == 3).unwrap().change()
+        );
+
+        let table = comparison_markdown(&comparisons, 0.1);
+        assert!(table
+            .contains("| 2024 | day-01 | part1 | 42.47 µs | 63.7 µs | +50.0% **regression** |"));
+        assert!(table.contains("| 2024 | day-01 | part2 | 61.47 µs | 64.54 µs | +5.0% |"));
+        assert!(table.contains("| 2024 | day-03 | part1 | - | 63.7 µs | new |"));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let table = Results::from_divan(2024, DIVAN).to_markdown();
+        assert!(table.starts_with("| year | day | bench | median | mean | samples | allocs |\n"));
+        assert!(table.contains("| 2024 | day-01 | part1 | 42.47 µs | 43.3 µs | 100 | - |\n"));
+        assert!(table.contains("| 2024 | day-02 | parse | 244.6 ns | 251.8 ns | 100 | - |\n"));
+        assert!(table.contains("| 2024 | day-06 | part2 | 1.22 s | 1.227 s | 100 | - |\n"));
+    }
+
+    #[test]
+    fn test_format_ns() {
+        assert_eq!("53.99 ns", format_ns(53.99));
+        assert_eq!("362 µs", format_ns(362_000.0));
+        assert_eq!("1.103 ms", format_ns(1_103_000.0));
+        assert_eq!("3.526 s", format_ns(3.526e9));
+    }
+}
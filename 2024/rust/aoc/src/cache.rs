@@ -0,0 +1,170 @@
+//! On-disk cache of everything downloaded from the puzzle site, laid out as
+//! `<cache>/<year>/day-NN/<resource>` so the input loader finds cached inputs.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use aoc_common::input::cached_day_dir;
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Something of a day that can be downloaded from the puzzle site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    Puzzle,
+}
+
+impl Resource {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Resource::Input => "input.txt",
+            Resource::Puzzle => "puzzle.html",
+        }
+    }
+
+    /// Path of the resource on the puzzle site.
+    pub fn url_path(self, year: u16, day: u8) -> String {
+        match self {
+            Resource::Input => format!("/{year}/day/{day}/input"),
+            Resource::Puzzle => format!("/{year}/day/{day}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum CacheError {
+    #[error("failed to read {}", path.display())]
+    #[diagnostic(code(aoc::cache::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::cache::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in [`aoc_common::input::cache_dir`], if there is one.
+    pub fn from_env() -> Option<Self> {
+        aoc_common::input::cache_dir().map(Self::new)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, year: u16, day: u8, resource: Resource) -> PathBuf {
+        cached_day_dir(&self.dir, year, day).join(resource.file_name())
+    }
+
+    pub fn get(
+        &self,
+        year: u16,
+        day: u8,
+        resource: Resource,
+    ) -> Result<Option<String>, CacheError> {
+        let path = self.path(year, day, resource);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(CacheError::Read { path, source }),
+        }
+    }
+
+    pub fn put(
+        &self,
+        year: u16,
+        day: u8,
+        resource: Resource,
+        contents: &str,
+    ) -> Result<(), CacheError> {
+        write(&self.path(year, day, resource), contents)
+    }
+
+    /// Where the outcomes of submitted answers are remembered.
+    pub fn submissions_path(&self, year: u16, day: u8) -> PathBuf {
+        cached_day_dir(&self.dir, year, day).join("submissions.txt")
+    }
+
+    /// When the last live request was sent by any process using this cache.
+    pub fn last_request(&self) -> Option<SystemTime> {
+        fs::metadata(self.last_request_path())
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    pub fn mark_request(&self) -> Result<(), CacheError> {
+        write(&self.last_request_path(), "")
+    }
+
+    fn last_request_path(&self) -> PathBuf {
+        self.dir.join("last-request")
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), CacheError> {
+    let write_err = |source| CacheError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_err)?;
+    }
+    fs::write(path, contents).map_err(write_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::Input;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+
+        assert_eq!(None, cache.get(2024, 3, Resource::Input)?);
+        cache.put(2024, 3, Resource::Input, "mul(2,4)")?;
+        cache.put(2024, 3, Resource::Puzzle, "<html></html>")?;
+        assert_eq!(
+            Some("mul(2,4)".to_string()),
+            cache.get(2024, 3, Resource::Input)?
+        );
+        assert_eq!(
+            Some("<html></html>".to_string()),
+            cache.get(2024, 3, Resource::Puzzle)?
+        );
+        assert_eq!(None, cache.get(2024, 4, Resource::Input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_loader_finds_cached_input() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        cache.put(2024, 3, Resource::Input, "mul(2,4)")?;
+
+        let input = Input::new(2024, 3).with_cache_dir(Some(dir.path().to_path_buf()));
+        assert_eq!("mul(2,4)", input.load()?);
+        Ok(())
+    }
+}
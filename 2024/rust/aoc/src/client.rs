@@ -0,0 +1,586 @@
+//! Talks to the puzzle site on behalf of the user owning the `SESSION` cookie.
+//!
+//! Every response is cached (see [`Cache`]) and live requests are spaced by at
+//! least [`DEFAULT_MIN_DELAY`], so careless re-runs do not hit the site again.
+use std::{
+    cell::Cell,
+    env, io, thread,
+    time::{Duration, SystemTime},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{
+    cache::{Cache, CacheError, Resource},
+    registry::Part,
+    submit::Outcome,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// The site asks automated tools to say who is running them.
+pub const DEFAULT_USER_AGENT: &str = "github.com/paulora2405/advent-of-code by paulora2405";
+pub const DEFAULT_MIN_DELAY: Duration = Duration::from_secs(5);
+
+/// Bodies the site answers with instead of an input when the session is not
+/// accepted, sometimes with a successful status.
+const LOGGED_OUT_BODIES: &[&str] = &[
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+    "Puzzle inputs differ by user. Please log in",
+];
+const LOCKED_BODY: &str = "Please don't repeatedly request this endpoint before it unlocks!";
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    user_agent: String,
+    agent: ureq::Agent,
+    cache: Option<Cache>,
+    offline: bool,
+    refresh: bool,
+    min_delay: Duration,
+    last_request: Cell<Option<SystemTime>>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ClientError {
+    #[error("`SESSION` is not set")]
+    #[diagnostic(
+        code(aoc::client::session),
+        help("copy the `session` cookie of {BASE_URL} into `.env` as `SESSION=...`, the justfile explains how to find it")
+    )]
+    MissingSession,
+    #[error("{url} is not cached and requests are disabled by `--offline`")]
+    #[diagnostic(code(aoc::client::offline))]
+    Offline { url: String },
+    #[error("request to {url} failed")]
+    #[diagnostic(code(aoc::client::request))]
+    Request {
+        url: String,
+        #[source]
+        source: Box<ureq::Error>,
+    },
+    #[error("the session was rejected by {url}")]
+    #[diagnostic(
+        code(aoc::client::logged_out),
+        help("`SESSION` is invalid or expired, copy a fresh `session` cookie of {BASE_URL} into `.env`")
+    )]
+    LoggedOut { url: String },
+    #[error("{url} is not unlocked yet")]
+    #[diagnostic(
+        code(aoc::client::locked),
+        help("puzzles unlock at midnight EST, try again later")
+    )]
+    Locked { url: String },
+    #[error("{url} answered with status {status}")]
+    #[diagnostic(code(aoc::client::status))]
+    Status { url: String, status: u16 },
+    #[error("{url} answered with `{content_type}` instead of `{expected}`")]
+    #[diagnostic(
+        code(aoc::client::content_type),
+        help("this is usually an error page, check that `SESSION` is still valid")
+    )]
+    ContentType {
+        url: String,
+        content_type: String,
+        expected: &'static str,
+    },
+    #[error("could not make sense of the response of {url}")]
+    #[diagnostic(
+        code(aoc::client::unexpected),
+        help("the answer may still have been accepted, check the puzzle page")
+    )]
+    UnexpectedResponse { url: String },
+    #[error("failed to read the response of {url}")]
+    #[diagnostic(code(aoc::client::read))]
+    Read {
+        url: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Cache(#[from] CacheError),
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            session: Some(session.into()),
+            ..Self::anonymous()
+        }
+    }
+
+    /// Builds a client for the session in the `SESSION` environment variable
+    /// that caches into [`Cache::from_env`]. A missing session is only
+    /// reported once a live request is needed.
+    pub fn from_env() -> Self {
+        let session = env::var("SESSION")
+            .ok()
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+        Self {
+            session,
+            cache: Cache::from_env(),
+            ..Self::anonymous()
+        }
+    }
+
+    fn anonymous() -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            session: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            agent: ureq::AgentBuilder::new().build(),
+            cache: None,
+            offline: false,
+            refresh: false,
+            min_delay: DEFAULT_MIN_DELAY,
+            last_request: Cell::new(None),
+        }
+    }
+
+    /// Sends every request to `base_url` instead of the puzzle site.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Only serves responses from the cache, failing instead of sending requests.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Ignores cached responses, the new responses are still cached.
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Waits until at least `min_delay` passed since the previous live request.
+    pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
+        self.min_delay = min_delay;
+        self
+    }
+
+    /// Gets the input of a puzzle, making sure the response is an input and
+    /// not one of the error pages of the site.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.resource(year, day, Resource::Input)
+    }
+
+    /// Gets the description of a puzzle as html, it only contains part 2 once
+    /// part 1 is solved so use [`Client::with_refresh`] to update it.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.resource(year, day, Resource::Puzzle)
+    }
+
+    fn resource(&self, year: u16, day: u8, resource: Resource) -> Result<String, ClientError> {
+        let url = format!("{}{}", self.base_url, resource.url_path(year, day));
+        if let Some(cache) = self.cache.as_ref().filter(|_| !self.refresh) {
+            if let Some(cached) = cache.get(year, day, resource)? {
+                tracing::debug!(url, "serving from cache");
+                return Ok(cached);
+            }
+        }
+        if self.offline {
+            return Err(ClientError::Offline { url });
+        }
+
+        let (content_type, body) = self.send(&url, &[])?;
+        if is_logged_out(&body) {
+            return Err(ClientError::LoggedOut { url });
+        }
+        if body.trim() == LOCKED_BODY {
+            return Err(ClientError::Locked { url });
+        }
+        let expected = match resource {
+            Resource::Input => "text/plain",
+            Resource::Puzzle => "text/html",
+        };
+        if content_type != expected {
+            return Err(ClientError::ContentType {
+                url,
+                content_type,
+                expected,
+            });
+        }
+
+        if let Some(cache) = &self.cache {
+            cache.put(year, day, resource, &body)?;
+        }
+        Ok(body)
+    }
+
+    /// Submits `answer` for a part of a puzzle, this is never cached.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Outcome, ClientError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        if self.offline {
+            return Err(ClientError::Offline { url });
+        }
+        let level = part.to_string();
+        let (_, body) = self.send(&url, &[("level", &level), ("answer", answer)])?;
+        Outcome::parse(&body).ok_or(ClientError::UnexpectedResponse { url })
+    }
+
+    /// Sends a GET, or a POST when there is a `form`, and returns the content
+    /// type and body of a successful response.
+    #[tracing::instrument(skip(self))]
+    fn send(&self, url: &str, form: &[(&str, &str)]) -> Result<(String, String), ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::MissingSession)?;
+        self.throttle()?;
+        let request = self
+            .agent
+            .request(if form.is_empty() { "GET" } else { "POST" }, url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", &self.user_agent);
+        let response = match if form.is_empty() {
+            request.call()
+        } else {
+            request.send_form(form)
+        } {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                let url = url.to_string();
+                let body = response.into_string().unwrap_or_default();
+                return Err(if is_logged_out(&body) {
+                    ClientError::LoggedOut { url }
+                } else if status == 404 && body.trim() == LOCKED_BODY {
+                    ClientError::Locked { url }
+                } else {
+                    ClientError::Status { url, status }
+                });
+            }
+            Err(source) => {
+                return Err(ClientError::Request {
+                    url: url.to_string(),
+                    source: Box::new(source),
+                })
+            }
+        };
+        let content_type = response.content_type().to_string();
+        let body = response.into_string().map_err(|source| ClientError::Read {
+            url: url.to_string(),
+            source,
+        })?;
+        Ok((content_type, body))
+    }
+
+    /// Sleeps until `min_delay` passed since the last live request of this
+    /// client, or of any client sharing its cache.
+    fn throttle(&self) -> Result<(), ClientError> {
+        let last_request = self
+            .last_request
+            .get()
+            .max(self.cache.as_ref().and_then(Cache::last_request));
+        if let Some(elapsed) = last_request.and_then(|last| last.elapsed().ok()) {
+            if let Some(wait) = self.min_delay.checked_sub(elapsed) {
+                tracing::info!(?wait, "waiting before the next request");
+                thread::sleep(wait);
+            }
+        }
+
+        self.last_request.set(Some(SystemTime::now()));
+        if let Some(cache) = &self.cache {
+            cache.mark_request()?;
+        }
+        Ok(())
+    }
+}
+
+fn is_logged_out(body: &str) -> bool {
+    LOGGED_OUT_BODIES
+        .iter()
+        .any(|known| body.trim_start().starts_with(known))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use super::*;
+
+    /// Serves the same canned response on a local port for every request,
+    /// remembering the head and body of each request.
+    struct StandIn {
+        base_url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StandIn {
+        fn new(status: u16, content_type: &str, body: &str) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let response = format!(
+                "HTTP/1.1 {status} Stand-in\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let requests = Arc::new(Mutex::new(vec![]));
+            let received = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request = String::new();
+                    while reader.read_line(&mut request).unwrap() > 0
+                        && !request.ends_with("\r\n\r\n")
+                    {}
+                    let length = request
+                        .lines()
+                        .find_map(|line| line.strip_prefix("Content-Length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    request.push_str(&String::from_utf8(body).unwrap());
+                    received.lock().unwrap().push(request);
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+            Self { base_url, requests }
+        }
+
+        fn client(&self) -> Client {
+            Client::new("cookie")
+                .with_base_url(&self.base_url)
+                .with_min_delay(Duration::ZERO)
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn fetch(status: u16, content_type: &str, body: &str) -> Result<String, ClientError> {
+        StandIn::new(status, content_type, body)
+            .client()
+            .input(2024, 1)
+    }
+
+    #[test]
+    fn test_input() -> miette::Result<()> {
+        assert_eq!(
+            "3   4\n4   3\n",
+            fetch(200, "text/plain", "3   4\n4   3\n")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_logged_out() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        assert!(matches!(
+            fetch(400, "text/plain", body),
+            Err(ClientError::LoggedOut { .. })
+        ));
+        assert!(matches!(
+            fetch(200, "text/plain", body),
+            Err(ClientError::LoggedOut { .. })
+        ));
+        // a failing server is not mistaken for a bad session
+        assert!(matches!(
+            fetch(500, "text/html", "<html>Internal Server Error</html>"),
+            Err(ClientError::Status { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_locked() {
+        assert!(matches!(
+            fetch(404, "text/plain", LOCKED_BODY),
+            Err(ClientError::Locked { .. })
+        ));
+        assert!(matches!(
+            fetch(404, "text/html", "<html>Not Found</html>"),
+            Err(ClientError::Status { status: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn test_content_type() {
+        assert!(matches!(
+            fetch(200, "text/html", "<!DOCTYPE html><html></html>"),
+            Err(ClientError::ContentType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cache() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let stand_in = StandIn::new(200, "text/plain", "3   4\n");
+        let client = stand_in.client().with_cache(Some(Cache::new(dir.path())));
+
+        assert_eq!("3   4\n", client.input(2024, 1)?);
+        assert_eq!("3   4\n", client.input(2024, 1)?);
+        assert_eq!(1, stand_in.requests().len());
+        assert_eq!(
+            "3   4\n",
+            std::fs::read_to_string(Cache::new(dir.path()).path(2024, 1, Resource::Input)).unwrap()
+        );
+
+        client.clone().with_refresh(true).input(2024, 1)?;
+        assert_eq!(2, stand_in.requests().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let client = StandIn::new(
+            400,
+            "text/plain",
+            "Puzzle inputs differ by user. Please log in",
+        )
+        .client()
+        .with_cache(Some(cache.clone()));
+
+        assert!(client.input(2024, 1).is_err());
+        assert!(!cache.path(2024, 1, Resource::Input).exists());
+    }
+
+    #[test]
+    fn test_offline() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let stand_in = StandIn::new(200, "text/plain", "3   4\n");
+        let client = stand_in
+            .client()
+            .with_cache(Some(cache.clone()))
+            .with_offline(true);
+
+        assert!(matches!(
+            client.input(2024, 1),
+            Err(ClientError::Offline { .. })
+        ));
+        cache.put(2024, 1, Resource::Input, "cached")?;
+        assert_eq!("cached", client.input(2024, 1)?);
+        assert!(stand_in.requests().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_offline_needs_no_session() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        cache.put(2024, 1, Resource::Puzzle, "<html></html>")?;
+        let client = Client::anonymous()
+            .with_cache(Some(cache))
+            .with_offline(true);
+
+        assert_eq!("<html></html>", client.puzzle(2024, 1)?);
+        assert!(matches!(
+            Client::anonymous().input(2024, 1),
+            Err(ClientError::MissingSession)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_agent() -> miette::Result<()> {
+        let stand_in = StandIn::new(200, "text/html", "<html></html>");
+        stand_in.client().puzzle(2024, 1)?;
+        stand_in
+            .client()
+            .with_user_agent("someone@example.com")
+            .puzzle(2024, 1)?;
+
+        let requests = stand_in.requests();
+        assert!(requests[0].starts_with("GET /2024/day/1 "));
+        assert!(requests[0].contains(&format!("User-Agent: {DEFAULT_USER_AGENT}\r\n")));
+        assert!(requests[0].contains("Cookie: session=cookie\r\n"));
+        assert!(requests[1].contains("User-Agent: someone@example.com\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_delay() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let stand_in = StandIn::new(200, "text/plain", "3   4\n");
+        let delay = Duration::from_millis(200);
+        let client = stand_in
+            .client()
+            .with_min_delay(delay)
+            .with_cache(Some(Cache::new(dir.path())));
+
+        let start = Instant::now();
+        client.input(2024, 1)?;
+        client.input(2024, 2)?;
+        assert!(start.elapsed() >= delay);
+
+        // a separate client sharing the cache waits as well
+        let start = Instant::now();
+        stand_in
+            .client()
+            .with_min_delay(delay)
+            .with_cache(Some(Cache::new(dir.path())))
+            .input(2024, 3)?;
+        assert!(start.elapsed() >= delay / 2);
+        assert_eq!(3, stand_in.requests().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit() -> miette::Result<()> {
+        let stand_in = StandIn::new(
+            200,
+            "text/html",
+            include_str!("../fixtures/submit-too-low.html"),
+        );
+        let outcome = stand_in.client().submit(2024, 12, Part::Two, "1 2")?;
+
+        assert_eq!(
+            Outcome::Incorrect(Some(crate::submit::Hint::TooLow)),
+            outcome
+        );
+        let requests = stand_in.requests();
+        assert!(requests[0].starts_with("POST /2024/day/12/answer "));
+        assert!(requests[0].contains("Content-Type: application/x-www-form-urlencoded\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=1+2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit_unexpected_response() {
+        let stand_in = StandIn::new(200, "text/html", "<html></html>");
+        assert!(matches!(
+            stand_in.client().submit(2024, 12, Part::One, "1"),
+            Err(ClientError::UnexpectedResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_submit_offline() {
+        let stand_in = StandIn::new(200, "text/html", "<html></html>");
+        assert!(matches!(
+            stand_in
+                .client()
+                .with_offline(true)
+                .submit(2024, 12, Part::One, "1"),
+            Err(ClientError::Offline { .. })
+        ));
+        assert!(stand_in.requests().is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+//! Turns the `<pre><code>` blocks of a puzzle description into
+//! `day-NN/examples/*.txt` files and rstest cases in the day's part files.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Expected answer written into generated cases, to be replaced by hand.
+pub const PLACEHOLDER: &str = "TODO";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ExamplesError {
+    #[error("failed to read {}", path.display())]
+    #[diagnostic(code(aoc::examples::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::examples::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Files touched by [`generate`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Generated {
+    pub examples: Vec<PathBuf>,
+    pub tests: Vec<PathBuf>,
+}
+
+/// Code blocks of every part of a puzzle description, in order.
+///
+/// Each part lives in its own `<article class="day-desc">`, the second one
+/// only shows up once part 1 is solved.
+pub fn extract(html: &str) -> Vec<Vec<String>> {
+    let articles = between(html, r#"<article class="day-desc">"#, "</article>");
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+    articles
+        .into_iter()
+        .map(|article| {
+            between(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .map(|block| decode(&strip_tags(block)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Writes the code blocks of `html` into `day_dir/examples` and adds a case
+/// per block to the tests of the matching part.
+///
+/// Blocks repeated by part 2 share the file of part 1. Example files that are
+/// not empty and parts whose `test_examples` has answers filled in are left
+/// alone, so this can run again once part 2 is unlocked.
+pub fn generate(day_dir: &Path, html: &str) -> Result<Generated, ExamplesError> {
+    let mut generated = Generated::default();
+    let mut blocks: Vec<String> = vec![];
+
+    for (part, part_blocks) in extract(html).into_iter().enumerate() {
+        let mut files = vec![];
+        for block in part_blocks {
+            let number = match blocks.iter().position(|known| *known == block) {
+                Some(index) => index + 1,
+                None => {
+                    blocks.push(block.clone());
+                    let path = day_dir
+                        .join("examples")
+                        .join(format!("{}.txt", blocks.len()));
+                    if fs::metadata(&path).map_or(true, |file| file.len() == 0) {
+                        write(&path, &block)?;
+                        generated.examples.push(path);
+                    }
+                    blocks.len()
+                }
+            };
+            files.push(number);
+        }
+
+        let path = day_dir.join("src").join(format!("part{}.rs", part + 1));
+        if files.is_empty() || !path.is_file() {
+            continue;
+        }
+        let source = fs::read_to_string(&path).map_err(|source| ExamplesError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        if let Some(source) = add_cases(&source, &files) {
+            write(&path, &source)?;
+            generated.tests.push(path);
+        }
+    }
+
+    Ok(generated)
+}
+
+/// Sets the cases of the rstest test over `examples/{n}.txt` in the tests
+/// module of `source`, adding the test if there is none.
+///
+/// The cases of an existing test are only replaced while they all still
+/// expect [`PLACEHOLDER`], like the one `daily-template` starts with. The
+/// test parses with the day's [`aoc_common::Solution`] and compares the
+/// displayed answer, so the module must import `Day` and the trait.
+fn add_cases(source: &str, examples: &[usize]) -> Option<String> {
+    let cases = examples
+        .iter()
+        .map(|n| {
+            format!(
+                "    #[case::example_{n}(include_str!(\"../examples/{n}.txt\"), \"{PLACEHOLDER}\")]\n"
+            )
+        })
+        .collect::<String>();
+    if source.contains("fn test_examples(") {
+        return replace_cases(source, &cases);
+    }
+
+    let module = source.find("mod tests {")?;
+    // right after the imports the module starts with
+    let mut insert_at = module + source[module..].find('\n')? + 1;
+    let mut offset = insert_at;
+    for line in source[insert_at..].split_inclusive('\n') {
+        offset += line.len();
+        match line.trim() {
+            import if import.starts_with("use ") => insert_at = offset,
+            "" => {}
+            _ => break,
+        }
+    }
+
+    let test = format!(
+        "    use rstest::rstest;
+
+    #[rstest]
+{cases}    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {{
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
+        Ok(())
+    }}
+"
+    );
+    let (head, tail) = source.split_at(insert_at);
+    let separator = if tail.starts_with('\n') { "" } else { "\n" };
+    Some(format!("{head}{test}{separator}{tail}"))
+}
+
+/// Swaps the `#[case]` lines of `source` for `cases`, unless one of them
+/// already has an answer.
+fn replace_cases(source: &str, cases: &str) -> Option<String> {
+    let is_case = |line: &str| line.trim_start().starts_with("#[case");
+    let placeholder = format!("\"{PLACEHOLDER}\")]");
+    if source
+        .lines()
+        .filter(|line| is_case(line))
+        .any(|line| !line.trim_end().ends_with(&placeholder))
+    {
+        return None;
+    }
+
+    let mut replaced = String::with_capacity(source.len() + cases.len());
+    let mut inserted = false;
+    for line in source.split_inclusive('\n') {
+        if !is_case(line) {
+            replaced.push_str(line);
+        } else if !inserted {
+            replaced.push_str(cases);
+            inserted = true;
+        }
+    }
+    (inserted && replaced != source).then_some(replaced)
+}
+
+/// Every piece of `text` between `start` and the next `end`.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut pieces = vec![];
+    let mut rest = text;
+    while let Some(from) = rest.find(start) {
+        rest = &rest[from + start.len()..];
+        let Some(to) = rest.find(end) else {
+            break;
+        };
+        pieces.push(&rest[..to]);
+        rest = &rest[to + end.len()..];
+    }
+    pieces
+}
+
+/// Drops the markup the site uses for highlighting, like `<em>`.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ExamplesError> {
+    let write_err = |source| ExamplesError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_err)?;
+    }
+    fs::write(path, contents).map_err(write_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = include_str!("../fixtures/puzzle-part1.html");
+    const BOTH_PARTS: &str = include_str!("../fixtures/puzzle-both-parts.html");
+    const TEMPLATE_PART: &str = include_str!("../../daily-template/src/part1.rs");
+
+    fn day_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        for part in ["part1.rs", "part2.rs"] {
+            fs::write(dir.path().join("src").join(part), TEMPLATE_PART).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_extract() {
+        let parts = extract(BOTH_PARTS);
+        assert_eq!(2, parts.len());
+        assert_eq!(
+            vec![
+                "..X...\n.SAMX.\n.A..A.\nXMAS.S\n.X....\n".to_string(),
+                "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n".to_string(),
+            ],
+            parts[0]
+        );
+        assert_eq!(
+            vec![
+                "M.S\n.A.\nM.S\n".to_string(),
+                "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n".to_string(),
+            ],
+            parts[1]
+        );
+    }
+
+    #[test]
+    fn test_extract_decodes_entities() {
+        let parts = extract("<pre><code>a -&gt; b &amp;&amp; <em>c</em> &lt; d\n</code></pre>");
+        assert_eq!(vec![vec!["a -> b && c < d\n".to_string()]], parts);
+    }
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let dir = day_dir();
+        let generated = generate(dir.path(), PART1)?;
+
+        assert_eq!(
+            vec![
+                dir.path().join("examples/1.txt"),
+                dir.path().join("examples/2.txt")
+            ],
+            generated.examples
+        );
+        assert_eq!(vec![dir.path().join("src/part1.rs")], generated.tests);
+        assert_eq!(
+            "..X...\n.SAMX.\n.A..A.\nXMAS.S\n.X....\n",
+            fs::read_to_string(dir.path().join("examples/1.txt")).unwrap()
+        );
+
+        let part1 = fs::read_to_string(dir.path().join("src/part1.rs")).unwrap();
+        assert!(part1.contains(
+            "    #[rstest]\n    #[case::example_1(include_str!(\"../examples/1.txt\"), \"TODO\")]\n    #[case::example_2(include_str!(\"../examples/2.txt\"), \"TODO\")]\n    #[test_log::test]\n    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {\n"
+        ));
+        assert_eq!(2, part1.matches("#[case::").count());
+        assert_eq!(
+            TEMPLATE_PART,
+            fs::read_to_string(dir.path().join("src/part2.rs")).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_after_part2_unlocks() -> miette::Result<()> {
+        let dir = day_dir();
+        generate(dir.path(), PART1)?;
+        fs::write(dir.path().join("examples/1.txt"), "edited by hand").unwrap();
+        let part1 = fs::read_to_string(dir.path().join("src/part1.rs")).unwrap();
+
+        let generated = generate(dir.path(), BOTH_PARTS)?;
+        assert_eq!(vec![dir.path().join("examples/3.txt")], generated.examples);
+        assert_eq!(vec![dir.path().join("src/part2.rs")], generated.tests);
+        assert_eq!(
+            "edited by hand",
+            fs::read_to_string(dir.path().join("examples/1.txt")).unwrap()
+        );
+        assert_eq!(
+            part1,
+            fs::read_to_string(dir.path().join("src/part1.rs")).unwrap()
+        );
+
+        let part2 = fs::read_to_string(dir.path().join("src/part2.rs")).unwrap();
+        assert!(part2.contains("#[case::example_3(include_str!(\"../examples/3.txt\"), \"TODO\")]\n    #[case::example_2(include_str!(\"../examples/2.txt\"), \"TODO\")]\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_keeps_answers() -> miette::Result<()> {
+        let dir = day_dir();
+        let answered = TEMPLATE_PART.replace("\"TODO\")]", "\"18\")]");
+        fs::write(dir.path().join("src/part1.rs"), &answered).unwrap();
+
+        let generated = generate(dir.path(), PART1)?;
+        assert!(generated.tests.is_empty());
+        assert_eq!(
+            answered,
+            fs::read_to_string(dir.path().join("src/part1.rs")).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_cases_without_test() {
+        let source = "fn process() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_parse() {}\n}\n";
+        let source = add_cases(source, &[1]).unwrap();
+        assert!(source.contains(
+            "    use super::*;\n    use rstest::rstest;\n\n    #[rstest]\n    #[case::example_1("
+        ));
+        assert!(source.contains("    }\n\n    #[test]\n    fn test_parse() {}\n"));
+    }
+}
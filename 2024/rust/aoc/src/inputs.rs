@@ -0,0 +1,243 @@
+//! Moves inputs between the plaintext files of the days and the encrypted
+//! store, see [`aoc_common::store`].
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::store::{self, stored_input_path, Secret, StoreError, STORE_DIR};
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Plaintext inputs of a day, the first one that exists is the input.
+const PLAINTEXT: [&str; 3] = ["input.txt", "input1.txt", "input2.txt"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Migrated {
+    Written(PathBuf),
+    /// The target already had the same input.
+    Unchanged(PathBuf),
+    /// There was nothing to migrate.
+    Missing,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum InputsError {
+    #[error("{} already exists with a different input", path.display())]
+    #[diagnostic(code(aoc::inputs::exists), help("pass `--force` to overwrite it"))]
+    Exists { path: PathBuf },
+    #[error("failed to read {}", path.display())]
+    #[diagnostic(code(aoc::inputs::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::inputs::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to use encrypted input {}", path.display())]
+    #[diagnostic(code(aoc::inputs::store))]
+    Store {
+        path: PathBuf,
+        #[source]
+        #[diagnostic_source]
+        source: StoreError,
+    },
+}
+
+/// Encrypts the plaintext input of `day-NN` into the store of `workspace_dir`,
+/// deleting the plaintext files holding it when `remove` is set.
+pub fn encrypt(
+    workspace_dir: &Path,
+    secret: &Secret,
+    year: u16,
+    day: u8,
+    force: bool,
+    remove: bool,
+) -> Result<Migrated, InputsError> {
+    let day_dir = workspace_dir.join(format!("day-{day:02}"));
+    let Some(input) = PLAINTEXT
+        .iter()
+        .map(|name| day_dir.join(name))
+        .find(|path| path.is_file())
+    else {
+        return Ok(Migrated::Missing);
+    };
+    let input = read_to_string(&input)?;
+
+    let path = stored_input_path(&workspace_dir.join(STORE_DIR), year, day);
+    // a fresh salt changes the whole file, so only rewrite it when needed
+    let migrated = match stored(secret, year, day, &path)? {
+        Some(stored) if stored == input => Migrated::Unchanged(path),
+        Some(_) if !force => return Err(InputsError::Exists { path }),
+        _ => {
+            let encrypted =
+                store::encrypt(secret, year, day, &input).map_err(|source| InputsError::Store {
+                    path: path.clone(),
+                    source,
+                })?;
+            write(&path, &encrypted)?;
+            Migrated::Written(path)
+        }
+    };
+
+    if remove {
+        for plaintext in PLAINTEXT.iter().map(|name| day_dir.join(name)) {
+            if plaintext.is_file() && read_to_string(&plaintext)? == input {
+                fs::remove_file(&plaintext).map_err(|source| InputsError::Write {
+                    path: plaintext,
+                    source,
+                })?;
+            }
+        }
+    }
+    Ok(migrated)
+}
+
+/// Decrypts the stored input of `day-NN` into its `input.txt`.
+pub fn decrypt(
+    workspace_dir: &Path,
+    secret: &Secret,
+    year: u16,
+    day: u8,
+    force: bool,
+) -> Result<Migrated, InputsError> {
+    let stored_path = stored_input_path(&workspace_dir.join(STORE_DIR), year, day);
+    let Some(input) = stored(secret, year, day, &stored_path)? else {
+        return Ok(Migrated::Missing);
+    };
+
+    let path = workspace_dir
+        .join(format!("day-{day:02}"))
+        .join("input.txt");
+    if path.is_file() {
+        if read_to_string(&path)? == input {
+            return Ok(Migrated::Unchanged(path));
+        }
+        if !force {
+            return Err(InputsError::Exists { path });
+        }
+    }
+    write(&path, input.as_bytes())?;
+    Ok(Migrated::Written(path))
+}
+
+/// Decrypts the input at `path`, if there is one.
+fn stored(secret: &Secret, year: u16, day: u8, path: &Path) -> Result<Option<String>, InputsError> {
+    let stored = match fs::read(path) {
+        Ok(stored) => stored,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(InputsError::Read {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    store::decrypt(secret, year, day, &stored)
+        .map(Some)
+        .map_err(|source| InputsError::Store {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+fn read_to_string(path: &Path) -> Result<String, InputsError> {
+    fs::read_to_string(path).map_err(|source| InputsError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<(), InputsError> {
+    let write_err = |source| InputsError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_err)?;
+    }
+    fs::write(path, contents).map_err(write_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input::Input;
+
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("day-01")).unwrap();
+        fs::write(dir.path().join("day-01/input1.txt"), "3   4\n").unwrap();
+        fs::write(dir.path().join("day-01/input2.txt"), "3   4\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_migrate() -> miette::Result<()> {
+        let workspace = workspace();
+        let secret = Secret::new("secret");
+        let stored = stored_input_path(&workspace.path().join(STORE_DIR), 2024, 1);
+
+        assert_eq!(
+            Migrated::Written(stored.clone()),
+            encrypt(workspace.path(), &secret, 2024, 1, false, true)?
+        );
+        assert!(!workspace.path().join("day-01/input1.txt").exists());
+        assert!(!workspace.path().join("day-01/input2.txt").exists());
+
+        // the runner and benches read it straight from the store
+        let input = Input::new(2024, 1)
+            .with_day_dir(workspace.path().join("day-01"))
+            .with_cache_dir(None)
+            .with_secret(Some(secret.clone()));
+        assert_eq!("3   4\n", input.load()?);
+
+        let input_path = workspace.path().join("day-01/input.txt");
+        assert_eq!(
+            Migrated::Written(input_path.clone()),
+            decrypt(workspace.path(), &secret, 2024, 1, false)?
+        );
+        assert_eq!("3   4\n", fs::read_to_string(&input_path).unwrap());
+
+        let before = fs::read(&stored).unwrap();
+        assert_eq!(
+            Migrated::Unchanged(stored.clone()),
+            encrypt(workspace.path(), &secret, 2024, 1, false, false)?
+        );
+        assert_eq!(before, fs::read(&stored).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_refuses_to_overwrite() -> miette::Result<()> {
+        let workspace = workspace();
+        let secret = Secret::new("secret");
+        encrypt(workspace.path(), &secret, 2024, 1, false, false)?;
+        fs::write(workspace.path().join("day-01/input.txt"), "other\n").unwrap();
+
+        assert!(matches!(
+            decrypt(workspace.path(), &secret, 2024, 1, false),
+            Err(InputsError::Exists { .. })
+        ));
+        assert!(matches!(
+            encrypt(workspace.path(), &secret, 2024, 1, false, false),
+            Err(InputsError::Exists { .. })
+        ));
+        assert!(matches!(
+            encrypt(workspace.path(), &secret, 2024, 1, true, false)?,
+            Migrated::Written(_)
+        ));
+        assert_eq!(
+            Migrated::Missing,
+            decrypt(workspace.path(), &secret, 2024, 2, false)?
+        );
+        Ok(())
+    }
+}
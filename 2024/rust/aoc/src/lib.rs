@@ -0,0 +1,9 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod examples;
+pub mod inputs;
+pub mod registry;
+pub mod scaffold;
+pub mod submit;
@@ -0,0 +1,684 @@
+use std::{fs, path::PathBuf};
+
+use aoc::{
+    answers::{Answer, Answers, Verdict},
+    bench::{self, Results},
+    cache::Cache,
+    client::{Client, DEFAULT_USER_AGENT},
+    examples,
+    inputs::{self, Migrated},
+    registry::{self, workspace_dir, Day, Measured, Part, Solved, DAYS},
+    scaffold::scaffold,
+    submit::{Hint, Outcome, Submissions},
+};
+use aoc_common::{
+    input::{Input, InputArgs},
+    store::{Secret, StoreError},
+};
+use clap::{Args, Parser, Subcommand};
+use miette::{miette, Context, IntoDiagnostic};
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOC: aoc_common::alloc::CountingAllocator = aoc_common::alloc::CountingAllocator::new();
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the solutions of one or more days and time them
+    Run(Selection),
+    /// Check the solutions of one or more days against their known answers
+    Verify(VerifyArgs),
+    /// Download the input of a day into `day-NN/input.txt`
+    Fetch(FetchArgs),
+    /// Download the puzzle description of a day and turn its examples
+    /// into `day-NN/examples/*.txt` and test cases
+    Examples(ExamplesArgs),
+    /// Submit the answer of a part and remember what the site said about it
+    Submit(SubmitArgs),
+    /// Create the crate of a new day from `daily-template`
+    Scaffold(ScaffoldArgs),
+    /// Encrypt the plaintext inputs of days into the `inputs` store
+    Encrypt(EncryptArgs),
+    /// Decrypt inputs from the `inputs` store into `day-NN/input.txt`
+    Decrypt(DecryptArgs),
+    /// Collect, summarize and compare the results of the benches
+    #[command(subcommand)]
+    Bench(BenchCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchCommand {
+    /// Run the benches of one or more days and store their results
+    Run(BenchRunArgs),
+    /// Compare the stored results against a baseline, failing on
+    /// regressions
+    Compare(BenchCompareArgs),
+    /// Regenerate the markdown summary of the stored results
+    Summary(BenchFiles),
+}
+
+#[derive(Args, Debug)]
+struct PuzzleArgs {
+    /// year of the puzzle
+    #[clap(long, default_value_t = registry::YEAR)]
+    year: u16,
+    /// day of the puzzle, either `12` or `day-12`
+    #[clap(short, long, value_parser = registry::parse_day)]
+    day: u8,
+}
+
+#[derive(Args, Debug)]
+struct ClientArgs {
+    /// user agent sent with every request, the
+    /// site asks for a way to contact you
+    #[clap(long, env = "AOC_USER_AGENT", default_value = DEFAULT_USER_AGENT)]
+    user_agent: String,
+}
+
+#[derive(Args, Debug)]
+struct CacheArgs {
+    /// only use responses cached by earlier
+    /// requests, never contact the site
+    #[clap(long, conflicts_with = "refresh")]
+    offline: bool,
+    /// ignore cached responses and request
+    /// them again
+    #[clap(long)]
+    refresh: bool,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Args, Debug)]
+struct FetchArgs {
+    #[command(flatten)]
+    puzzle: PuzzleArgs,
+    #[command(flatten)]
+    client: CacheArgs,
+    /// overwrite the day's `input.txt` if it
+    /// already exists
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+struct ExamplesArgs {
+    #[command(flatten)]
+    puzzle: PuzzleArgs,
+    #[command(flatten)]
+    client: CacheArgs,
+}
+
+#[derive(Args, Debug)]
+struct SubmitArgs {
+    #[command(flatten)]
+    puzzle: PuzzleArgs,
+    /// part of the puzzle
+    #[clap(short, long)]
+    part: Part,
+    /// answer to submit
+    #[clap(required_unless_present = "from_run", conflicts_with = "from_run")]
+    answer: Option<String>,
+    /// submit the answer of the registered
+    /// solution instead
+    #[clap(long)]
+    from_run: bool,
+    /// record a correct answer in plaintext
+    /// instead of as a salted hash
+    #[clap(long)]
+    plaintext: bool,
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Args, Debug)]
+struct ScaffoldArgs {
+    #[command(flatten)]
+    puzzle: PuzzleArgs,
+}
+
+#[derive(Args, Debug)]
+struct StoreSelection {
+    /// year of the puzzles
+    #[clap(long, default_value_t = registry::YEAR)]
+    year: u16,
+    /// day of the puzzle, either `12` or `day-12`
+    #[clap(short, long, value_parser = registry::parse_day, required_unless_present = "all")]
+    day: Option<u8>,
+    /// select every registered day of the year
+    #[clap(long, conflicts_with = "day")]
+    all: bool,
+}
+
+#[derive(Args, Debug)]
+struct EncryptArgs {
+    #[command(flatten)]
+    selection: StoreSelection,
+    /// replace stored inputs that differ from
+    /// the plaintext ones
+    #[clap(long)]
+    force: bool,
+    /// delete the plaintext inputs once they
+    /// are stored
+    #[clap(long)]
+    remove: bool,
+}
+
+#[derive(Args, Debug)]
+struct DecryptArgs {
+    #[command(flatten)]
+    selection: StoreSelection,
+    /// overwrite `input.txt` files that differ
+    /// from the stored inputs
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+struct BenchFiles {
+    /// results of the benches, relative to the
+    /// workspace
+    #[clap(long, default_value = bench::RESULTS_FILE)]
+    results: PathBuf,
+    /// markdown summary of the results, relative
+    /// to the workspace
+    #[clap(long, default_value = bench::SUMMARY_FILE)]
+    summary: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct BenchRunArgs {
+    #[command(flatten)]
+    selection: StoreSelection,
+    #[command(flatten)]
+    files: BenchFiles,
+}
+
+#[derive(Args, Debug)]
+struct BenchCompareArgs {
+    /// results to compare against, relative to
+    /// the workspace
+    #[clap(long, default_value = bench::BASELINE_FILE)]
+    baseline: PathBuf,
+    /// results of the benches, relative to the
+    /// workspace
+    #[clap(long, default_value = bench::RESULTS_FILE)]
+    results: PathBuf,
+    /// how much slower the median of a bench may
+    /// get before it is a regression, in percent
+    #[clap(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Args, Debug)]
+struct Selection {
+    /// year of the puzzle, e.g. `2024`
+    #[clap(required_unless_present = "all")]
+    year: Option<u16>,
+    /// day of the puzzle, every day of the year
+    /// is selected when omitted
+    #[clap(value_parser = registry::parse_day)]
+    day: Option<u8>,
+    /// part of the puzzle, both parts are
+    /// selected when omitted
+    part: Option<Part>,
+    /// select every registered day
+    #[clap(long, conflicts_with_all = ["year", "day", "part", "input"])]
+    all: bool,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// store the answers of parts that have no known
+    /// answer yet in the day's `answers.txt`
+    #[clap(long)]
+    record: bool,
+    /// record answers in plaintext instead of as a
+    /// salted hash
+    #[clap(long, requires = "record")]
+    plaintext: bool,
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Cli::parse().command {
+        Command::Run(selection) => run(&selection),
+        Command::Verify(args) => verify(&args),
+        Command::Fetch(args) => fetch(&args),
+        Command::Examples(args) => fetch_examples(&args),
+        Command::Submit(args) => submit(&args),
+        Command::Scaffold(args) => {
+            let PuzzleArgs { year, day } = args.puzzle;
+            let day_dir = scaffold(workspace_dir(), year, day)?;
+            println!("created {}", day_dir.display());
+            Ok(())
+        }
+        Command::Encrypt(args) => migrate(&args.selection, "encrypted", |secret, year, day| {
+            inputs::encrypt(workspace_dir(), secret, year, day, args.force, args.remove)
+        }),
+        Command::Decrypt(args) => migrate(&args.selection, "decrypted", |secret, year, day| {
+            inputs::decrypt(workspace_dir(), secret, year, day, args.force)
+        }),
+        Command::Bench(BenchCommand::Run(args)) => bench_run(&args),
+        Command::Bench(BenchCommand::Compare(args)) => bench_compare(&args),
+        Command::Bench(BenchCommand::Summary(files)) => {
+            let results = Results::load(&workspace_dir().join(&files.results))?;
+            write_summary(&files, &results)
+        }
+    }
+}
+
+impl Selection {
+    fn days(&self) -> miette::Result<Vec<&'static Day>> {
+        let days = DAYS
+            .iter()
+            .filter(|d| self.year.is_none_or(|year| d.year == year))
+            .filter(|d| self.day.is_none_or(|day| d.day == day))
+            .collect::<Vec<_>>();
+        if days.is_empty() {
+            let year = self.year.unwrap_or(registry::YEAR);
+            return Err(match self.day {
+                Some(day) => miette!("no solution registered for {year} day {day}"),
+                None => miette!("no solution registered for {year}"),
+            });
+        }
+        if days.len() > 1 && self.input.input.is_some() {
+            return Err(miette!(
+                "`--input` can only be used when selecting a single day"
+            ));
+        }
+        Ok(days)
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        self.part.map_or(Part::ALL.to_vec(), |part| vec![part])
+    }
+
+    fn input(&self, day: &Day) -> miette::Result<String> {
+        Ok(Input::new(day.year, day.day)
+            .with_day_dir(day.dir())
+            .with_path(self.input.input.clone())
+            .load()?)
+    }
+}
+
+impl ClientArgs {
+    fn client(&self) -> Client {
+        Client::from_env().with_user_agent(&self.user_agent)
+    }
+}
+
+impl CacheArgs {
+    fn client(&self) -> Client {
+        self.client
+            .client()
+            .with_offline(self.offline)
+            .with_refresh(self.refresh)
+    }
+}
+
+fn run(selection: &Selection) -> miette::Result<()> {
+    let parts = selection.parts();
+
+    let mut failures = 0;
+    for day in selection.days()? {
+        let input = match selection.input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                failures += parts.len();
+                eprintln!("{e:?}");
+                continue;
+            }
+        };
+        for &part in &parts {
+            match solve(day, part, &input) {
+                Ok(solved) => {
+                    println!(
+                        "{} {} part {part}: {} ({})",
+                        day.year,
+                        day.name(),
+                        solved.answer,
+                        timings(&solved)
+                    );
+                }
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("{e:?}");
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        Err(miette!("{failures} part(s) failed"))
+    } else {
+        Ok(())
+    }
+}
+
+fn verify(args: &VerifyArgs) -> miette::Result<()> {
+    let parts = args.selection.parts();
+
+    let mut failures = 0;
+    for day in args.selection.days()? {
+        let input = match args.selection.input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                failures += parts.len();
+                eprintln!("{e:?}");
+                continue;
+            }
+        };
+        let mut answers = Answers::load(&day.answers_path())?;
+        let mut recorded = false;
+
+        for &part in &parts {
+            let solved = match solve(day, part, &input) {
+                Ok(solved) => solved,
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("{e:?}");
+                    continue;
+                }
+            };
+            let answer = &solved.answer;
+            let verdict = answers.check(part, answer);
+            let note = match verdict {
+                Verdict::Match => String::new(),
+                Verdict::Mismatch => {
+                    failures += 1;
+                    match answers.get(part) {
+                        Some(Answer::Plain(expected)) => format!(", expected {expected}"),
+                        _ => String::new(),
+                    }
+                }
+                Verdict::Unknown if args.record => {
+                    let known = if args.plaintext {
+                        Answer::Plain(answer.clone())
+                    } else {
+                        Answer::hashed(answer)
+                    };
+                    recorded |= answers.record(part, known);
+                    ", recorded".to_string()
+                }
+                Verdict::Unknown => String::new(),
+            };
+            println!(
+                "{} {} part {part}: {verdict} {answer}{note} ({})",
+                day.year,
+                day.name(),
+                timings(&solved)
+            );
+        }
+
+        if recorded {
+            answers.save(&day.answers_path())?;
+        }
+    }
+
+    if failures > 0 {
+        Err(miette!("{failures} part(s) failed verification"))
+    } else {
+        Ok(())
+    }
+}
+
+fn fetch(args: &FetchArgs) -> miette::Result<()> {
+    let PuzzleArgs { year, day } = args.puzzle;
+    let day_dir = existing_day_dir(day)?;
+    let path = day_dir.join("input.txt");
+    if path.exists() && !args.force {
+        return Err(miette!(
+            help = "pass `--force` to overwrite it, and `--refresh` to download it again",
+            "{} already exists",
+            path.display()
+        ));
+    }
+
+    let input = args.client.client().input(year, day)?;
+    // write next to the input first so a failed write never clobbers a good one
+    let partial = day_dir.join("input.partial.txt");
+    fs::write(&partial, input)
+        .and_then(|()| fs::rename(&partial, &path))
+        .into_diagnostic()
+        .wrap_err_with(|| format!("write {}", path.display()))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn fetch_examples(args: &ExamplesArgs) -> miette::Result<()> {
+    let PuzzleArgs { year, day } = args.puzzle;
+    let day_dir = existing_day_dir(day)?;
+
+    let html = args.client.client().puzzle(year, day)?;
+    let generated = examples::generate(&day_dir, &html)?;
+    for path in &generated.examples {
+        println!("wrote {}", path.display());
+    }
+    for path in &generated.tests {
+        println!(
+            "added test cases to {}, fill in the expected answers",
+            path.display()
+        );
+    }
+    if generated.examples.is_empty() && generated.tests.is_empty() {
+        println!("no new examples found");
+    }
+    Ok(())
+}
+
+fn submit(args: &SubmitArgs) -> miette::Result<()> {
+    let PuzzleArgs { year, day } = args.puzzle;
+    let part = args.part;
+    let answer = match &args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => {
+            let registered = registry::find(year, day)
+                .ok_or_else(|| miette!("no solution registered for {year} day {day}"))?;
+            let input = Input::new(year, day)
+                .with_day_dir(registered.dir())
+                .with_path(args.input.input.clone())
+                .load()?;
+            let solved = solve(registered, part, &input)?;
+            println!(
+                "{year} {} part {part}: {} ({})",
+                registered.name(),
+                solved.answer,
+                timings(&solved)
+            );
+            solved.answer
+        }
+    };
+
+    let day_dir = workspace_dir().join(format!("day-{day:02}"));
+    let answers_path = day_dir.join("answers.txt");
+    let mut answers = Answers::load(&answers_path)?;
+    let submissions_path = Cache::from_env().map(|cache| cache.submissions_path(year, day));
+    let mut submissions = match &submissions_path {
+        Some(path) => Submissions::load(path)?,
+        None => Submissions::default(),
+    };
+    submissions.check(&answers, part, &answer)?;
+
+    let outcome = args.client.client().submit(year, day, part, &answer)?;
+    if let Some(path) = submissions_path.filter(|_| submissions.record(part, &answer, outcome)) {
+        submissions.save(&path)?;
+    }
+
+    match outcome {
+        Outcome::Correct => {
+            let known = if args.plaintext {
+                Answer::Plain(answer.clone())
+            } else {
+                Answer::hashed(&answer)
+            };
+            if day_dir.is_dir() && answers.record(part, known) {
+                answers.save(&answers_path)?;
+            }
+            println!("{year} day-{day:02} part {part}: {answer} is correct");
+            Ok(())
+        }
+        Outcome::Incorrect(hint) => Err(miette!(
+            "{answer} is not the right answer{}",
+            match hint {
+                Some(Hint::TooHigh) => ", it is too high",
+                Some(Hint::TooLow) => ", it is too low",
+                None => "",
+            }
+        )),
+        Outcome::RateLimited { wait } => Err(miette!(
+            help = wait.map_or("wait a bit".to_string(), |wait| format!(
+                "wait {wait:?} before submitting again"
+            )),
+            "an answer was submitted too recently, {answer} was not checked"
+        )),
+        Outcome::AlreadySolved => Err(miette!(
+            help = format!("record its answer with `aoc verify {year} {day} {part} --record`"),
+            "part {part} is already solved, {answer} was not checked"
+        )),
+    }
+}
+
+/// Runs `migrate` for every selected day, a single selected day must have
+/// something to migrate.
+fn migrate(
+    selection: &StoreSelection,
+    verb: &str,
+    migrate: impl Fn(&Secret, u16, u8) -> Result<Migrated, inputs::InputsError>,
+) -> miette::Result<()> {
+    let secret = Secret::from_env()?.ok_or(StoreError::NoKey)?;
+    let year = selection.year;
+    let days = match selection.day {
+        Some(day) => vec![day],
+        None => DAYS
+            .iter()
+            .filter(|d| d.year == year)
+            .map(|d| d.day)
+            .collect(),
+    };
+
+    for day in days {
+        match migrate(&secret, year, day)? {
+            Migrated::Written(path) => println!("{verb} {}", path.display()),
+            Migrated::Unchanged(path) => println!("{} is up to date", path.display()),
+            Migrated::Missing if selection.all => println!("{year} day-{day:02} has no input"),
+            Migrated::Missing => return Err(miette!("{year} day-{day:02} has no input")),
+        }
+    }
+    Ok(())
+}
+
+fn bench_run(args: &BenchRunArgs) -> miette::Result<()> {
+    let year = args.selection.year;
+    let days = DAYS
+        .iter()
+        .filter(|d| d.year == year)
+        .filter(|d| args.selection.day.is_none_or(|day| d.day == day))
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        return Err(miette!("no solution registered for {year}"));
+    }
+
+    let path = workspace_dir().join(&args.files.results);
+    let mut results = Results::load(&path)?;
+    for day in days {
+        eprintln!("benchmarking {year} {}", day.name());
+        let day_results = bench::run(workspace_dir(), day)?;
+        if day_results.measurements.is_empty() {
+            return Err(miette!(
+                "found no results in the output of the benches of {}",
+                day.name()
+            ));
+        }
+        results.merge(day_results);
+        // saved after every day, so a failing day keeps the others
+        results.save(&path)?;
+    }
+    println!("wrote {}", path.display());
+    write_summary(&args.files, &results)
+}
+
+fn bench_compare(args: &BenchCompareArgs) -> miette::Result<()> {
+    let baseline_path = workspace_dir().join(&args.baseline);
+    if !baseline_path.is_file() {
+        return Err(miette!(
+            help = format!(
+                "save a baseline with `cp {} {}`",
+                bench::RESULTS_FILE,
+                bench::BASELINE_FILE
+            ),
+            "{} does not exist",
+            baseline_path.display()
+        ));
+    }
+    let baseline = Results::load(&baseline_path)?;
+    let results = Results::load(&workspace_dir().join(&args.results))?;
+
+    let threshold = args.threshold / 100.0;
+    let comparisons = bench::compare(&baseline, &results);
+    print!("{}", bench::comparison_markdown(&comparisons, threshold));
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+    if regressions > 0 {
+        Err(miette!(
+            "{regressions} bench(es) regressed by more than {}%",
+            args.threshold
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn write_summary(files: &BenchFiles, results: &Results) -> miette::Result<()> {
+    let path = workspace_dir().join(&files.summary);
+    let summary = format!("# Benchmarks\n\n{}", results.to_markdown());
+    bench::write(&path, &summary)?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn existing_day_dir(day: u8) -> miette::Result<PathBuf> {
+    let day_dir = workspace_dir().join(format!("day-{day:02}"));
+    if !day_dir.is_dir() {
+        return Err(miette!(
+            help = format!("create it with `aoc scaffold --day {day}`"),
+            "{} does not exist",
+            day_dir.display()
+        ));
+    }
+    Ok(day_dir)
+}
+
+fn solve(day: &Day, part: Part, input: &str) -> miette::Result<Solved> {
+    (day.process)(input, part).wrap_err_with(|| format!("process {} part {part}", day.name()))
+}
+
+fn timings(solved: &Solved) -> String {
+    let step = |name, measured: &Measured| match measured.allocs {
+        Some(allocs) => format!("{name} {:?} [{allocs}]", measured.elapsed),
+        None => format!("{name} {:?}", measured.elapsed),
+    };
+    format!(
+        "{}, {}",
+        step("parse", &solved.parse),
+        step("solve", &solved.solve)
+    )
+}
@@ -0,0 +1,168 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{alloc::Allocs, Solution};
+pub use aoc_common::{Part, YEAR};
+
+/// Every registered day, sorted by year and day.
+pub static DAYS: &[Day] = include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Parses an input and solves a part of it, see [`process`].
+pub type Process = fn(&str, Part) -> miette::Result<Solved>;
+
+/// Answer of a part, and what parsing the input and solving took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub answer: String,
+    pub parse: Measured,
+    pub solve: Measured,
+}
+
+/// How long a step took and, with the `count-allocs` feature, what it
+/// allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measured {
+    pub elapsed: Duration,
+    pub allocs: Option<Allocs>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub process: Process,
+}
+
+impl Day {
+    /// Name of the crate and directory of this day, e.g. `day-01`.
+    pub fn name(&self) -> String {
+        format!("day-{:02}", self.day)
+    }
+
+    /// Directory of this day's crate.
+    pub fn dir(&self) -> PathBuf {
+        workspace_dir().join(self.name())
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("answers.txt")
+    }
+}
+
+/// Solves `part` of `input` with the solution `S` of a day, measuring the
+/// parse and the solve separately like the benches do.
+pub fn process<S: Solution>(input: &str, part: Part) -> miette::Result<Solved> {
+    let (parsed, parse) = measure(|| S::parse(input));
+    let parsed = parsed?;
+    let (answer, solve) = measure(|| S::part(&parsed, part));
+
+    Ok(Solved {
+        answer: answer.to_string(),
+        parse,
+        solve,
+    })
+}
+
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Measured) {
+    let ((value, elapsed), allocs) = aoc_common::alloc::count(|| {
+        let start = Instant::now();
+        let value = f();
+        (value, start.elapsed())
+    });
+    (value, Measured { elapsed, allocs })
+}
+
+/// Root of the cargo workspace all days live in.
+pub fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner should live inside the workspace")
+}
+
+/// Parses a day given either as a number or formatted like `day-01`,
+/// to match all other commands in the repo.
+pub fn parse_day(input: &str) -> Result<u8, String> {
+    let day = input
+        .strip_prefix("day-")
+        .unwrap_or(input)
+        .parse::<u8>()
+        .map_err(|_| format!("day `{input}` must be a number or formatted as `day-01`"))?;
+    if (1..=25).contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("day `{input}` must be between 1 and 25"))
+    }
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted() {
+        assert!(DAYS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    }
+
+    #[test]
+    fn test_find() {
+        let day = find(YEAR, 1).expect("day 1 should be registered");
+        assert_eq!("day-01", day.name());
+        assert!(find(YEAR, 26).is_none());
+        assert!(find(1999, 1).is_none());
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(input: &str) -> miette::Result<Self::Parsed> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|e| miette::miette!("{e}")))
+                .collect()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Self::Answer {
+            parsed.iter().sum()
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Self::Answer {
+            parsed.iter().product()
+        }
+    }
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("9", process::<Sum>("2 3 4", Part::One)?.answer);
+        assert_eq!("24", process::<Sum>("2 3 4", Part::Two)?.answer);
+        assert!(process::<Sum>("2 three", Part::One).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(Ok(1), parse_day("1"));
+        assert_eq!(Ok(1), parse_day("01"));
+        assert_eq!(Ok(12), parse_day("day-12"));
+        assert!(parse_day("day-26").is_err());
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("twelve").is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), "2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+}
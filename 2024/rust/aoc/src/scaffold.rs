@@ -0,0 +1,176 @@
+//! Creates a new day crate from `daily-template`, like `cargo generate` would,
+//! and registers it with the runner.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub const TEMPLATE_DIR: &str = "daily-template";
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ScaffoldError {
+    #[error("{} already exists", dir.display())]
+    #[diagnostic(code(aoc::scaffold::exists))]
+    AlreadyExists { dir: PathBuf },
+    #[error("failed to read {}", path.display())]
+    #[diagnostic(code(aoc::scaffold::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    #[diagnostic(code(aoc::scaffold::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Creates `day-NN` of `year` in `workspace_dir` and adds it to the
+/// dependencies of the runner, returning the directory of the new day.
+pub fn scaffold(workspace_dir: &Path, year: u16, day: u8) -> Result<PathBuf, ScaffoldError> {
+    let name = format!("day-{day:02}");
+    let day_dir = workspace_dir.join(&name);
+    if day_dir.exists() {
+        return Err(ScaffoldError::AlreadyExists { dir: day_dir });
+    }
+
+    copy_template(&workspace_dir.join(TEMPLATE_DIR), &day_dir, &name, year)?;
+    register(&workspace_dir.join("aoc").join("Cargo.toml"), &name)?;
+
+    Ok(day_dir)
+}
+
+fn copy_template(from: &Path, to: &Path, name: &str, year: u16) -> Result<(), ScaffoldError> {
+    let read_err = |source| ScaffoldError::Read {
+        path: from.to_path_buf(),
+        source,
+    };
+    fs::create_dir_all(to).map_err(|source| ScaffoldError::Write {
+        path: to.to_path_buf(),
+        source,
+    })?;
+
+    for entry in fs::read_dir(from).map_err(read_err)? {
+        let entry = entry.map_err(read_err)?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_template(&path, &target, name, year)?;
+        } else if entry.file_name() != "cargo-generate.toml" {
+            let contents = fs::read_to_string(&path).map_err(|source| ScaffoldError::Read {
+                path: path.clone(),
+                source,
+            })?;
+            let contents = contents
+                .replace("{{project-name}}", name)
+                .replace("{{crate_name}}", &name.replace('-', "_"))
+                .replace("{{year}}", &year.to_string());
+            fs::write(&target, contents).map_err(|source| ScaffoldError::Write {
+                path: target.clone(),
+                source,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `name` as a path dependency right after the last day in `manifest`.
+fn register(manifest: &Path, name: &str) -> Result<(), ScaffoldError> {
+    let contents = fs::read_to_string(manifest).map_err(|source| ScaffoldError::Read {
+        path: manifest.to_path_buf(),
+        source,
+    })?;
+    let mut lines = contents.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines
+        .iter()
+        .any(|line| line.starts_with(&format!("{name} ")))
+    {
+        return Ok(());
+    }
+
+    let dependency = format!("{name} = {{ path = \"../{name}\" }}");
+    match lines.iter().rposition(|line| line.starts_with("day-")) {
+        Some(last_day) => lines.insert(last_day + 1, dependency),
+        None => {
+            let dependencies = lines
+                .iter()
+                .position(|line| line == "[dependencies]")
+                .unwrap_or(lines.len());
+            lines.insert((dependencies + 1).min(lines.len()), dependency);
+        }
+    }
+
+    fs::write(manifest, lines.join("\n") + "\n").map_err(|source| ScaffoldError::Write {
+        path: manifest.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::YEAR;
+
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join(TEMPLATE_DIR);
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::write(
+            template.join("Cargo.toml"),
+            "[package]\nname = \"{{project-name}}\"\n\n[package.metadata.aoc]\nyear = {{year}}\n",
+        )
+        .unwrap();
+        fs::write(template.join("cargo-generate.toml"), "[template]\n").unwrap();
+        fs::write(
+            template.join("src").join("main.rs"),
+            "use {{crate_name}}::part1;\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("aoc")).unwrap();
+        fs::write(
+            dir.path().join("aoc").join("Cargo.toml"),
+            "[dependencies]\nday-01 = { path = \"../day-01\" }\nday-02 = { path = \"../day-02\" }\n\n[dev-dependencies]\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scaffold() -> miette::Result<()> {
+        let workspace = workspace();
+        let day_dir = scaffold(workspace.path(), 2023, 16)?;
+
+        assert_eq!(
+            "[package]\nname = \"day-16\"\n\n[package.metadata.aoc]\nyear = 2023\n",
+            fs::read_to_string(day_dir.join("Cargo.toml")).unwrap()
+        );
+        assert_eq!(
+            "use day_16::part1;\n",
+            fs::read_to_string(day_dir.join("src").join("main.rs")).unwrap()
+        );
+        assert!(!day_dir.join("cargo-generate.toml").exists());
+        assert_eq!(
+            "[dependencies]\nday-01 = { path = \"../day-01\" }\nday-02 = { path = \"../day-02\" }\nday-16 = { path = \"../day-16\" }\n\n[dev-dependencies]\n",
+            fs::read_to_string(workspace.path().join("aoc").join("Cargo.toml")).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaffold_existing_day() {
+        let workspace = workspace();
+        fs::create_dir_all(workspace.path().join("day-02")).unwrap();
+
+        assert!(matches!(
+            scaffold(workspace.path(), YEAR, 2),
+            Err(ScaffoldError::AlreadyExists { .. })
+        ));
+    }
+}
@@ -0,0 +1,463 @@
+//! What the site said about submitted answers, remembered in the cache as
+//! `<cache>/<year>/day-NN/submissions.txt` like
+//!
+//! ```text
+//! part1: 1234 too-high
+//! part1: 17 incorrect
+//! part1: 618 correct
+//! ```
+//!
+//! so answers known to be wrong are never sent twice.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use miette::Diagnostic;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    character::complete::{multispace0, space0, space1},
+    combinator::{all_consuming, value},
+    multi::many0,
+    sequence::{pair, preceded, terminated, tuple},
+    IResult, Parser,
+};
+use thiserror::Error;
+
+use crate::{
+    answers::{Answers, Verdict},
+    registry::Part,
+};
+
+/// How the site classified a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// Another answer was sent too recently, `wait` is how long is left.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: Part,
+    pub answer: String,
+    /// Either [`Outcome::Correct`] or [`Outcome::Incorrect`].
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub submissions: Vec<Submission>,
+}
+
+/// Why an answer is not worth submitting.
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum Refusal {
+    #[error("part {part} is already solved with {answer}")]
+    #[diagnostic(code(aoc::submit::solved))]
+    Solved { part: Part, answer: String },
+    #[error("{answer} does not match the recorded answer of part {part}")]
+    #[diagnostic(
+        code(aoc::submit::mismatch),
+        help("the part is already solved, check `answers.txt`")
+    )]
+    Mismatch { part: Part, answer: String },
+    #[error("{answer} was already submitted for part {part} and is wrong")]
+    #[diagnostic(code(aoc::submit::known_wrong))]
+    KnownWrong { part: Part, answer: String },
+    #[error("{answer} is not below {bound}, which is already too high")]
+    #[diagnostic(code(aoc::submit::too_high))]
+    TooHigh { answer: String, bound: i128 },
+    #[error("{answer} is not above {bound}, which is already too low")]
+    #[diagnostic(code(aoc::submit::too_low))]
+    TooLow { answer: String, bound: i128 },
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum SubmissionsError {
+    #[error("failed to read submissions {}", path.display())]
+    #[diagnostic(code(aoc::submit::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to write submissions {}", path.display())]
+    #[diagnostic(code(aoc::submit::write))]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("malformed submissions file {}", path.display())]
+    #[diagnostic(
+        code(aoc::submit::parse),
+        help("every line must look like `part1: <answer> <correct|incorrect|too-high|too-low>`")
+    )]
+    Parse { path: PathBuf },
+}
+
+impl Outcome {
+    /// Classifies the page the site answers a submission with.
+    pub fn parse(html: &str) -> Option<Self> {
+        let text = html
+            .split_once("<article>")
+            .map_or(html, |(_, article)| article);
+        if text.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            Some(Outcome::Incorrect(
+                if text.contains("your answer is too high") {
+                    Some(Hint::TooHigh)
+                } else if text.contains("your answer is too low") {
+                    Some(Hint::TooLow)
+                } else {
+                    None
+                },
+            ))
+        } else if text.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited {
+                wait: text
+                    .split_once("You have ")
+                    .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                    .and_then(|(wait, _)| parse_wait(wait)),
+            })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect(None) => write!(f, "incorrect"),
+            Outcome::Incorrect(Some(Hint::TooHigh)) => write!(f, "too-high"),
+            Outcome::Incorrect(Some(Hint::TooLow)) => write!(f, "too-low"),
+            Outcome::RateLimited { wait: Some(wait) } => write!(f, "rate limited for {wait:?}"),
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+impl Submissions {
+    /// Reads the submissions at `path`, a missing file has none.
+    pub fn load(path: &Path) -> Result<Self, SubmissionsError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(SubmissionsError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let (_, submissions) = parse_submissions(&text).map_err(|_| SubmissionsError::Parse {
+            path: path.to_path_buf(),
+        })?;
+        Ok(Self { submissions })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmissionsError> {
+        let write_err = |source| SubmissionsError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        fs::write(path, self.to_string()).map_err(write_err)
+    }
+
+    /// Remembers `outcome` if it says anything about `answer`.
+    pub fn record(&mut self, part: Part, answer: &str, outcome: Outcome) -> bool {
+        if !matches!(outcome, Outcome::Correct | Outcome::Incorrect(_)) {
+            return false;
+        }
+        self.submissions.push(Submission {
+            part,
+            answer: answer.to_string(),
+            outcome,
+        });
+        true
+    }
+
+    /// Highest answer known to be too low and lowest known to be too high.
+    pub fn bounds(&self, part: Part) -> (Option<i128>, Option<i128>) {
+        let numbers = |hint| {
+            self.submissions
+                .iter()
+                .filter(move |s| s.part == part && s.outcome == Outcome::Incorrect(Some(hint)))
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+        (numbers(Hint::TooLow).max(), numbers(Hint::TooHigh).min())
+    }
+
+    /// Refuses answers that are known to be wrong, either because they were
+    /// submitted before, are outside the known bounds or differ from the
+    /// answer in `answers`.
+    pub fn check(&self, answers: &Answers, part: Part, answer: &str) -> Result<(), Refusal> {
+        let owned = || answer.to_string();
+        match answers.check(part, answer) {
+            Verdict::Match => {
+                return Err(Refusal::Solved {
+                    part,
+                    answer: owned(),
+                })
+            }
+            Verdict::Mismatch => {
+                return Err(Refusal::Mismatch {
+                    part,
+                    answer: owned(),
+                })
+            }
+            Verdict::Unknown => {}
+        }
+
+        for submission in self.submissions.iter().filter(|s| s.part == part) {
+            let same = submission.answer == answer;
+            match submission.outcome {
+                Outcome::Correct if same => {
+                    return Err(Refusal::Solved {
+                        part,
+                        answer: owned(),
+                    })
+                }
+                Outcome::Correct => {
+                    return Err(Refusal::Mismatch {
+                        part,
+                        answer: owned(),
+                    })
+                }
+                Outcome::Incorrect(_) if same => {
+                    return Err(Refusal::KnownWrong {
+                        part,
+                        answer: owned(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(number) = answer.parse::<i128>() {
+            let (too_low, too_high) = self.bounds(part);
+            if let Some(bound) = too_high.filter(|&bound| number >= bound) {
+                return Err(Refusal::TooHigh {
+                    answer: owned(),
+                    bound,
+                });
+            }
+            if let Some(bound) = too_low.filter(|&bound| number <= bound) {
+                return Err(Refusal::TooLow {
+                    answer: owned(),
+                    bound,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Submissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for Submission {
+            part,
+            answer,
+            outcome,
+        } in &self.submissions
+        {
+            writeln!(f, "part{part}: {answer} {outcome}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses waits like `38s` or `1m 2s`.
+fn parse_wait(wait: &str) -> Option<Duration> {
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, piece| {
+            let (amount, unit) = piece.split_at(piece.len().checked_sub(1)?);
+            let amount = amount.parse::<u64>().ok()?;
+            let seconds = match unit {
+                "h" => amount * 60 * 60,
+                "m" => amount * 60,
+                "s" => amount,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(seconds))
+        })
+}
+
+fn parse_submissions(input: &str) -> IResult<&str, Vec<Submission>> {
+    all_consuming(preceded(
+        multispace0,
+        many0(terminated(parse_line, multispace0)),
+    ))(input)
+}
+
+fn parse_line(input: &str) -> IResult<&str, Submission> {
+    tuple((
+        terminated(
+            alt((
+                value(Part::One, tag("part1")),
+                value(Part::Two, tag("part2")),
+            )),
+            pair(tag(":"), space0),
+        ),
+        terminated(
+            take_till1(|c: char| c.is_whitespace()).map(str::to_string),
+            space1,
+        ),
+        alt((
+            value(Outcome::Correct, tag("correct")),
+            value(Outcome::Incorrect(None), tag("incorrect")),
+            value(Outcome::Incorrect(Some(Hint::TooHigh)), tag("too-high")),
+            value(Outcome::Incorrect(Some(Hint::TooLow)), tag("too-low")),
+        )),
+    ))
+    .map(|(part, answer, outcome)| Submission {
+        part,
+        answer,
+        outcome,
+    })
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Answer;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_outcome() {
+        let cases = [
+            (
+                include_str!("../fixtures/submit-correct.html"),
+                Outcome::Correct,
+            ),
+            (
+                include_str!("../fixtures/submit-too-high.html"),
+                Outcome::Incorrect(Some(Hint::TooHigh)),
+            ),
+            (
+                include_str!("../fixtures/submit-too-low.html"),
+                Outcome::Incorrect(Some(Hint::TooLow)),
+            ),
+            (
+                include_str!("../fixtures/submit-incorrect.html"),
+                Outcome::Incorrect(None),
+            ),
+            (
+                include_str!("../fixtures/submit-rate-limited.html"),
+                Outcome::RateLimited {
+                    wait: Some(Duration::from_secs(62)),
+                },
+            ),
+            (
+                include_str!("../fixtures/submit-already-solved.html"),
+                Outcome::AlreadySolved,
+            ),
+        ];
+        for (html, outcome) in cases {
+            assert_eq!(Some(outcome), Outcome::parse(html));
+        }
+        assert_eq!(None, Outcome::parse("<html></html>"));
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(Some(Duration::from_secs(38)), parse_wait("38s"));
+        assert_eq!(Some(Duration::from_secs(3723)), parse_wait("1h 2m 3s"));
+        assert_eq!(None, parse_wait("soon"));
+    }
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let mut submissions = Submissions::default();
+        assert!(submissions.record(Part::One, "1234", Outcome::Incorrect(Some(Hint::TooHigh))));
+        assert!(submissions.record(Part::One, "abc", Outcome::Incorrect(None)));
+        assert!(submissions.record(Part::One, "618", Outcome::Correct));
+        assert!(!submissions.record(Part::Two, "5", Outcome::AlreadySolved));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2024/day-01/submissions.txt");
+        submissions.save(&path)?;
+
+        assert_eq!(
+            "part1: 1234 too-high\npart1: abc incorrect\npart1: 618 correct\n",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(submissions, Submissions::load(&path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let mut submissions = Submissions::default();
+        submissions.record(Part::Two, "100", Outcome::Incorrect(Some(Hint::TooHigh)));
+        submissions.record(Part::Two, "90", Outcome::Incorrect(Some(Hint::TooHigh)));
+        submissions.record(Part::Two, "10", Outcome::Incorrect(Some(Hint::TooLow)));
+        submissions.record(Part::Two, "42", Outcome::Incorrect(None));
+        let answers = Answers {
+            part1: Some(Answer::hashed("7")),
+            part2: None,
+        };
+
+        assert_eq!(Ok(()), submissions.check(&answers, Part::Two, "50"));
+        assert_eq!(
+            Ok(()),
+            submissions.check(&answers, Part::Two, "not a number")
+        );
+        assert!(matches!(
+            submissions.check(&answers, Part::Two, "42"),
+            Err(Refusal::KnownWrong { .. })
+        ));
+        assert_eq!(
+            Err(Refusal::TooHigh {
+                answer: "95".to_string(),
+                bound: 90
+            }),
+            submissions.check(&answers, Part::Two, "95")
+        );
+        assert_eq!(
+            Err(Refusal::TooLow {
+                answer: "5".to_string(),
+                bound: 10
+            }),
+            submissions.check(&answers, Part::Two, "5")
+        );
+        assert!(matches!(
+            submissions.check(&answers, Part::One, "7"),
+            Err(Refusal::Solved { .. })
+        ));
+        assert!(matches!(
+            submissions.check(&answers, Part::One, "8"),
+            Err(Refusal::Mismatch { .. })
+        ));
+
+        submissions.record(Part::Two, "60", Outcome::Correct);
+        assert!(matches!(
+            submissions.check(&Answers::default(), Part::Two, "61"),
+            Err(Refusal::Mismatch { .. })
+        ));
+    }
+}
@@ -1,2 +1,24 @@
+use aoc_common::Solution;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = String;
+    type Answer = u64;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(input.to_string())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
@@ -1,17 +1,22 @@
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
+pub fn process(input: &str) -> u64 {
     dbg!(input);
     todo!("day 01 - part 2");
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "";
-        assert_eq!("", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "TODO")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,2 +1,34 @@
+use aoc_common::Solution;
+use aoc_parse::lines;
+use nom::{
+    character::complete::{self, space1},
+    sequence::separated_pair,
+};
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    /// Both columns of location ids.
+    type Parsed = (Vec<i32>, Vec<i32>);
+    type Answer = i32;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        let pairs = aoc_parse::parse(
+            input,
+            lines(separated_pair(complete::i32, space1, complete::i32)),
+        )?;
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
@@ -1,22 +1,39 @@
-use day_02::*;
+use std::sync::LazyLock;
+
+use aoc_common::Solution;
+use day_02::Day;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler<aoc_common::alloc::CountingAllocator> =
+    divan::AllocProfiler::new(aoc_common::alloc::CountingAllocator::new());
+
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_common::input!().load().expect("should find the input"));
+/// Parsed once up front, so the parts are timed on their own.
+static PARSED: LazyLock<<Day as Solution>::Parsed> =
+    LazyLock::new(|| Day::parse(&INPUT).expect("should parse the input"));
 
 fn main() {
+    LazyLock::force(&PARSED);
+    // What one run of every step allocates, for `aoc bench` to store.
+    #[cfg(feature = "count-allocs")]
+    aoc_common::alloc::report::<Day>(env!("CARGO_CRATE_NAME"), &INPUT);
     // Run registered benchmarks.
     divan::main();
 }
 
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
+fn parse() -> <Day as Solution>::Parsed {
+    Day::parse(divan::black_box(&INPUT)).unwrap()
+}
+
+#[divan::bench]
+fn part1() -> <Day as Solution>::Answer {
+    Day::part1(divan::black_box(&PARSED))
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
+fn part2() -> <Day as Solution>::Answer {
+    Day::part2(divan::black_box(&PARSED))
 }
\ No newline at end of file
@@ -1,12 +1,13 @@
-use day_02::part1::process;
+use aoc_common::{Part, Solution};
+use day_02::Day;
 use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let file = aoc_common::input!().with_args().load()?;
+    let result = Day::solve(&file, Part::One).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
\ No newline at end of file
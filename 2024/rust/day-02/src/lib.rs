@@ -1,2 +1,32 @@
+use aoc_common::Solution;
+use aoc_parse::{number_rows, IResult, Span};
+use nom::Parser;
+
 pub mod part1;
 pub mod part2;
+
+pub type Report = Vec<i32>;
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<Report>;
+    type Answer = usize;
+
+    #[tracing::instrument(skip(input))]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Vec<Report>> {
+    number_rows().parse(input)
+}
@@ -1,21 +1,14 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let reports = input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+use crate::Report;
 
+#[tracing::instrument(skip(reports))]
+pub fn process(reports: &[Report]) -> usize {
     let mut safe_reports = 0;
 
     for report in reports {
-        safe_reports += if is_report_safe(&report) { 1 } else { 0 };
+        safe_reports += if is_report_safe(report) { 1 } else { 0 };
     }
 
-    Ok(safe_reports.to_string())
+    safe_reports
 }
 
 fn is_report_safe(report: &[i32]) -> bool {
@@ -32,17 +25,17 @@ fn is_report_safe(report: &[i32]) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        assert_eq!("2", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "2")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,29 +1,16 @@
-use miette::miette;
-use nom::{
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
-    IResult,
-};
-
-type Report = Vec<i32>;
-
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, reports) = parse_input(input).map_err(|e| miette!("parse failed {}", e))?;
+use crate::Report;
 
+#[tracing::instrument(skip(reports))]
+pub fn process(reports: &[Report]) -> usize {
     let mut safe_reports = 0;
 
     for report in reports {
-        if is_report_safe(&report, true) {
+        if is_report_safe(report, true) {
             safe_reports += 1;
         }
     }
 
-    Ok(safe_reports.to_string())
-}
-
-fn parse_input(input: &str) -> IResult<&str, Vec<Report>> {
-    separated_list1(newline, separated_list1(space1, complete::i32))(input)
+    safe_reports
 }
 
 #[tracing::instrument(ret)]
@@ -51,25 +38,18 @@ fn is_report_safe(report: &[i32], damper_on: bool) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::Solution;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        assert_eq!("4", process(input)?);
-        Ok(())
-    }
-
-    #[test]
-    fn test_edge_case() -> miette::Result<()> {
-        let input = "3 2 3 4 5
-1 2 3 4 3";
-        assert_eq!("2", process(input)?);
+    use super::*;
+    use crate::Day;
+
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "4")]
+    #[case::edge_case(include_str!("../examples/2.txt"), "2")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
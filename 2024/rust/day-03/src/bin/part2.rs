@@ -1,12 +1,13 @@
-use day_03::part2::process;
+use aoc_common::{Part, Solution};
+use day_03::Day;
 use miette::Context;
 
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    let file = aoc_common::input!().with_args().load()?;
+    let result = Day::solve(&file, Part::Two).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -1,2 +1,68 @@
+use aoc_common::Solution;
+use aoc_parse::{IResult, Span};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, anychar},
+    combinator::{rest, value},
+    multi::{many1, many_till},
+    sequence::{delimited, separated_pair, terminated},
+    Parser,
+};
+
 pub mod part1;
 pub mod part2;
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<Instruction>;
+    type Answer = u32;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Vec<Instruction>> {
+    // the memory is corrupted, everything around the instructions is noise
+    terminated(
+        many1(many_till(anychar, parse_instruction).map(|(_discarded, ins)| ins)),
+        rest,
+    )(input)
+}
+
+fn parse_instruction(input: Span) -> IResult<Instruction> {
+    alt((
+        value(Instruction::Dont, tag("don't()")),
+        value(Instruction::Do, tag("do()")),
+        parse_mul,
+    ))(input)
+}
+
+fn parse_mul(input: Span) -> IResult<Instruction> {
+    let (input, _) = tag("mul")(input)?;
+    let (input, pair) = delimited(
+        tag("("),
+        separated_pair(complete::u32, tag(","), complete::u32),
+        tag(")"),
+    )(input)?;
+
+    Ok((input, Instruction::Mul(pair.0, pair.1)))
+}
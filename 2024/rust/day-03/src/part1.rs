@@ -1,53 +1,29 @@
-use miette::miette;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, anychar},
-    multi::{many1, many_till},
-    sequence::{delimited, separated_pair},
-    IResult, Parser,
-};
+use crate::Instruction;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, instructions) = parse_input(input).map_err(|e| miette!("parse error: {}", e))?;
-
-    let sum: u32 = instructions
+pub fn process(instructions: &[Instruction]) -> u32 {
+    instructions
         .iter()
         .map(|ins| match ins {
             Instruction::Mul(x, y) => x * y,
+            Instruction::Do | Instruction::Dont => 0,
         })
-        .sum();
-
-    Ok(sum.to_string())
-}
-
-#[derive(Debug)]
-enum Instruction {
-    Mul(u32, u32),
-}
-
-fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
-    let (input, _) = tag("mul")(input)?;
-    let (input, pair) = delimited(
-        tag("("),
-        separated_pair(complete::u32, tag(","), complete::u32),
-        tag(")"),
-    )(input)?;
-
-    Ok((input, Instruction::Mul(pair.0, pair.1)))
-}
-fn parse_input(input: &str) -> IResult<&str, Vec<Instruction>> {
-    many1(many_till(anychar, parse_instruction).map(|(_discarded, ins)| ins))(input)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!("161", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "161")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,25 +1,7 @@
-use miette::miette;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, anychar},
-    combinator::value,
-    multi::{many1, many_till},
-    sequence::{delimited, separated_pair},
-    IResult, Parser,
-};
-
-#[derive(Debug, Clone)]
-enum Instruction {
-    Mul(u32, u32),
-    Do,
-    Dont,
-}
+use crate::Instruction;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_input, instructions) = parse_input(input).map_err(|e| miette!("parse error: {}", e))?;
-
+pub fn process(instructions: &[Instruction]) -> u32 {
     let (_, result) = instructions
         .iter()
         .fold((true, 0), |(should_process, acc), ins| match ins {
@@ -34,40 +16,22 @@ pub fn process(input: &str) -> miette::Result<String> {
             }
         });
 
-    Ok(result.to_string())
-}
-
-fn parse_input(input: &str) -> IResult<&str, Vec<Instruction>> {
-    many1(many_till(anychar, parse_instruction).map(|(_discarded, ins)| ins))(input)
-}
-
-fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
-    alt((
-        value(Instruction::Dont, tag("don't()")),
-        value(Instruction::Do, tag("do()")),
-        parse_mul,
-    ))(input)
-}
-
-fn parse_mul(input: &str) -> IResult<&str, Instruction> {
-    let (input, _) = tag("mul")(input)?;
-    let (input, pair) = delimited(
-        tag("("),
-        separated_pair(complete::u32, tag(","), complete::u32),
-        tag(")"),
-    )(input)?;
-
-    Ok((input, Instruction::Mul(pair.0, pair.1)))
+    result
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!("48", process(input)?);
+    #[rstest]
+    #[case::example_2(include_str!("../examples/2.txt"), "48")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
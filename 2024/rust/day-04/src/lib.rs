@@ -1,2 +1,26 @@
+use aoc_common::Solution;
+use aoc_grid::Grid;
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    /// The word search.
+    type Parsed = Grid<char>;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(Grid::parse(input, |c| c)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
@@ -1,85 +1,37 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let lines = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
+use aoc_grid::{Grid, ADJ8};
 
-    let mut words_found = 0;
-    let offsets = [-1, 0, 1];
-    let letters = ['X', 'M', 'A', 'S'];
+const WORD: &str = "XMAS";
 
-    for row in 0..lines.len() {
-        for col in 0..lines[0].len() {
-            for row_off in offsets {
-                for col_off in offsets {
-                    if letters_match(&lines, &letters, (row, col), (row_off, col_off)) {
-                        words_found += 1;
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(words_found.to_string())
-}
-
-fn letters_match(
-    lines: &Vec<Vec<char>>,
-    letters: &[char],
-    (row, col): (usize, usize),
-    (offset_row, offset_col): (isize, isize),
-) -> bool {
-    if lines.get(row).is_some()
-        && lines[row].get(col).is_some()
-        && letters.first().is_some_and(|&c| c == lines[row][col])
-    {
-        if letters.len() == 1 {
-            return true;
-        }
-        if let (Some(new_row), Some(new_col)) = (
-            row.checked_add_signed(offset_row),
-            col.checked_add_signed(offset_col),
-        ) {
-            return letters_match(
-                lines,
-                &letters[1..],
-                (new_row, new_col),
-                (offset_row, offset_col),
-            );
-        }
-    }
-    false
+#[tracing::instrument(skip(grid))]
+pub fn process(grid: &Grid<char>) -> usize {
+    grid.find_all(|&c| c == 'X')
+        .map(|start| {
+            ADJ8.into_iter()
+                .filter(|&step| {
+                    grid.ray(start, step)
+                        .map(|(_, &c)| c)
+                        .take(WORD.len())
+                        .eq(WORD.chars())
+                })
+                .count()
+        })
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::Solution;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!("18", process(input)?);
-        Ok(())
-    }
+    use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_simple() -> miette::Result<()> {
-        let input = "..X...
-.SAMX.
-.A..A.
-XMAS.S
-.X....";
-        assert_eq!("4", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "18")]
+    #[case::simple(include_str!("../examples/2.txt"), "4")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,47 +1,37 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let lines = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
+use aoc_grid::Grid;
+use glam::IVec2;
 
-    let mut words_found = 0;
-    let letters = ["MS", "SM"];
-
-    for row in 1..lines.len() - 1 {
-        for col in 1..lines[0].len() - 1 {
-            if lines[row][col] == 'A' {
-                let diag1 = format!("{}{}", lines[row - 1][col - 1], lines[row + 1][col + 1]);
-                let diag2 = format!("{}{}", lines[row - 1][col + 1], lines[row + 1][col - 1]);
-                if (diag1 == letters[0] || diag1 == letters[1])
-                    && (diag2 == letters[0] || diag2 == letters[1])
-                {
-                    words_found += 1;
-                }
-            }
-        }
-    }
+#[tracing::instrument(skip(grid))]
+pub fn process(grid: &Grid<char>) -> usize {
+    grid.find_all(|&c| c == 'A')
+        .filter(|&pos| {
+            is_mas(grid, pos, IVec2::NEG_ONE, IVec2::ONE)
+                && is_mas(grid, pos, IVec2::new(1, -1), IVec2::new(-1, 1))
+        })
+        .count()
+}
 
-    Ok(words_found.to_string())
+/// Whether the diagonal through `pos` reads `MAS` one way or the other.
+fn is_mas(grid: &Grid<char>, pos: IVec2, a: IVec2, b: IVec2) -> bool {
+    matches!(
+        (grid.get(pos + a), grid.get(pos + b)),
+        (Some('M'), Some('S')) | (Some('S'), Some('M'))
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!("9", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "9")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,2 +1,73 @@
+use std::collections::HashMap;
+
+use aoc_common::Solution;
+use aoc_parse::{lines, row, sections, unsigned, IResult, Span};
+use nom::{bytes::complete::tag, sequence::separated_pair, Parser};
+
 pub mod part1;
 pub mod part2;
+
+/// Pages that must come after each page.
+pub type Rules = HashMap<i32, Vec<i32>>;
+pub type Update = Vec<i32>;
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = (Rules, Vec<Update>);
+    type Answer = i32;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn is_valid_update(update: &Update, rules: &Rules) -> bool {
+    let mut index = 0;
+    let mut current_page;
+    let mut pages_before;
+    while index < update.len() {
+        current_page = update[index];
+        pages_before = &update[0..index];
+        if let Some(pages_that_must_be_after) = rules.get(&current_page) {
+            if pages_that_must_be_after
+                .iter()
+                .any(|page| pages_before.contains(page))
+            {
+                return false;
+            }
+        }
+        index += 1;
+    }
+    true
+}
+
+fn parse_input(input: Span) -> IResult<(Rules, Vec<Update>)> {
+    sections(parse_rules, parse_updates).parse(input)
+}
+
+fn parse_rules(input: Span) -> IResult<Rules> {
+    lines(separated_pair(unsigned(), tag("|"), unsigned()))
+        .map(|rules| {
+            rules
+                .into_iter()
+                .fold(Rules::new(), |mut acc, (page, after)| {
+                    acc.entry(page).or_default().push(after);
+                    acc
+                })
+        })
+        .parse(input)
+}
+
+fn parse_updates(input: Span) -> IResult<Vec<Update>> {
+    lines(row(unsigned(), tag(","))).parse(input)
+}
@@ -1,2 +1,55 @@
+use aoc_common::Solution;
+use aoc_grid::{Grid, GridCell};
+use glam::IVec2;
+use miette::miette;
+
 pub mod part1;
 pub mod part2;
+
+/// The mapped area of the lab.
+#[derive(Debug, Clone)]
+pub struct Lab {
+    /// Whether there is an obstacle on each position.
+    obstacles: Grid<bool>,
+    /// Where the guard starts, facing up.
+    start: IVec2,
+}
+
+/// What the map shows on a position of the lab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('.')]
+    Floor,
+    #[cell('#')]
+    Obstacle,
+    #[cell('^')]
+    Guard,
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Lab;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        let map = Grid::<Tile>::parse_cells(input)?;
+        let start = map
+            .find(|&tile| tile == Tile::Guard)
+            .ok_or_else(|| miette!("the map should have a guard"))?;
+
+        Ok(Lab {
+            obstacles: map.map(|&tile| tile == Tile::Obstacle),
+            start,
+        })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
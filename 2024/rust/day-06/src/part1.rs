@@ -1,99 +1,54 @@
-use std::{cmp::max, collections::HashSet};
+use aoc_grid::{Direction, DirectionSet, Grid};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Dir {
-    fn next(&self, pos: (usize, usize), n: usize, m: usize) -> Option<(usize, usize)> {
-        match self {
-            Dir::Up if pos.0 > 0 => Some((pos.0 - 1, pos.1)),
-            Dir::Right if pos.1 < m - 1 => Some((pos.0, pos.1 + 1)),
-            Dir::Down if pos.0 < n - 1 => Some((pos.0 + 1, pos.1)),
-            Dir::Left if pos.1 > 0 => Some((pos.0, pos.1 - 1)),
-            _ => None,
-        }
-    }
-
-    fn rotate(&mut self) {
-        *self = match self {
-            Dir::Up => Dir::Right,
-            Dir::Right => Dir::Down,
-            Dir::Down => Dir::Left,
-            Dir::Left => Dir::Up,
-        };
-    }
-}
-
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let lines = input.lines();
-
-    let (mut n, mut m) = (0, 0);
+use crate::Lab;
 
-    let mut obstacles = HashSet::<(usize, usize)>::new();
-    let mut curr_pos = (0, 0);
-    let mut dir = Dir::Up;
-    let mut visited = HashSet::<((usize, usize), Dir)>::new();
-
-    lines.enumerate().for_each(|(i, line)| {
-        n = max(n, i + 1);
-        line.chars().enumerate().for_each(|(j, c)| {
-            m = max(m, j + 1);
-            if c == '#' {
-                obstacles.insert((i, j));
-            } else if c == '^' {
-                curr_pos = (i, j);
-            }
-        });
-    });
+#[tracing::instrument(skip(lab))]
+pub fn process(lab: &Lab) -> usize {
+    let mut curr_pos = lab.start;
+    let mut dir = Direction::Up;
+    let mut visited = Grid::filled(
+        lab.obstacles.width(),
+        lab.obstacles.height(),
+        DirectionSet::new(),
+    );
 
     loop {
-        visited.insert((curr_pos, dir));
-        let next_pos = dir.next(curr_pos, n, m);
+        visited[curr_pos].insert(dir);
+        let next_pos = curr_pos + dir.delta();
 
-        if next_pos.is_some() {
-            if obstacles.contains(&next_pos.unwrap()) {
-                dir.rotate();
-            } else if visited.contains(&(next_pos.unwrap(), dir)) {
+        if let Some(&obstacle) = lab.obstacles.get(next_pos) {
+            if obstacle {
+                dir = dir.turn_right();
+            } else if visited[next_pos].contains(dir) {
                 break;
             } else {
-                curr_pos = next_pos.unwrap();
+                curr_pos = next_pos;
             }
         } else {
             break;
         }
     }
 
-    Ok(visited
+    visited
+        .cells()
         .iter()
-        .map(|(pos, _)| pos)
-        .collect::<HashSet<_>>()
-        .len()
-        .to_string())
+        .filter(|dirs| !dirs.is_empty())
+        .count()
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-        assert_eq!("41", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "41")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,69 +1,27 @@
+use aoc_common::cycle::brent;
+use aoc_grid::Direction;
+use glam::IVec2;
 use rayon::prelude::*;
-use std::{cmp::max, collections::HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Dir {
-    fn next(&self, pos: (usize, usize), n: usize, m: usize) -> Option<(usize, usize)> {
-        match self {
-            Dir::Up if pos.0 > 0 => Some((pos.0 - 1, pos.1)),
-            Dir::Right if pos.1 < m - 1 => Some((pos.0, pos.1 + 1)),
-            Dir::Down if pos.0 < n - 1 => Some((pos.0 + 1, pos.1)),
-            Dir::Left if pos.1 > 0 => Some((pos.0, pos.1 - 1)),
-            _ => None,
-        }
-    }
-
-    fn rotate(&mut self) {
-        *self = match self {
-            Dir::Up => Dir::Right,
-            Dir::Right => Dir::Down,
-            Dir::Down => Dir::Left,
-            Dir::Left => Dir::Up,
-        };
-    }
-}
+use crate::Lab;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let lines = input.lines();
+#[tracing::instrument(skip(lab))]
+pub fn process(lab: &Lab) -> usize {
+    let Lab {
+        obstacles,
+        start: init_pos,
+    } = lab;
+    let init_dir = Direction::Up;
 
-    let (mut n, mut m) = (0, 0);
+    let combinations: Vec<IVec2> = obstacles.positions().collect();
 
-    let mut obstacles = HashSet::<(usize, usize)>::new();
-    let mut init_pos = (0, 0);
-    let init_dir = Dir::Up;
-
-    lines.enumerate().for_each(|(i, line)| {
-        n = max(n, i + 1);
-        line.chars().enumerate().for_each(|(j, c)| {
-            m = max(m, j + 1);
-            if c == '#' {
-                obstacles.insert((i, j));
-            } else if c == '^' {
-                init_pos = (i, j);
-            }
-        });
-    });
-
-    let combinations: Vec<(usize, usize)> =
-        (0..n).flat_map(|i| (0..m).map(move |j| (i, j))).collect();
-
-    let positions_that_generate_loops: i32 = combinations
+    combinations
         .par_iter()
         .fold(
-            || 0i32,
-            |acc, (i, j)| {
-                let mut obstacles = obstacles.clone();
-                if init_pos != (*i, *j) && !obstacles.contains(&(*i, *j)) {
-                    obstacles.insert((*i, *j));
-                    if move_guard(init_pos, init_dir, &obstacles, n, m) {
+            || 0usize,
+            |acc, &extra| {
+                if *init_pos != extra && !obstacles[extra] {
+                    if move_guard(*init_pos, init_dir, lab, extra) {
                         acc + 1
                     } else {
                         acc
@@ -73,56 +31,37 @@ pub fn process(input: &str) -> miette::Result<String> {
                 }
             },
         )
-        .sum();
-
-    Ok(positions_that_generate_loops.to_string())
+        .sum()
 }
 
-fn move_guard(
-    init_pos: (usize, usize),
-    init_dir: Dir,
-    obstacles: &HashSet<(usize, usize)>,
-    n: usize,
-    m: usize,
-) -> bool {
-    let mut curr_pos = init_pos;
-    let mut dir = init_dir;
-    let mut visited = HashSet::<((usize, usize), Dir)>::new();
-    loop {
-        visited.insert((curr_pos, dir));
-        let next_pos = dir.next(curr_pos, n, m);
-
-        if next_pos.is_some() {
-            if obstacles.contains(&next_pos.unwrap()) {
-                dir.rotate();
-            } else if visited.contains(&(next_pos.unwrap(), dir)) {
-                return true;
-            } else {
-                curr_pos = next_pos.unwrap();
-            }
-        } else {
-            return false;
+/// Whether the guard ends up in a loop with an `extra` obstacle in the lab.
+fn move_guard(init_pos: IVec2, init_dir: Direction, lab: &Lab, extra: IVec2) -> bool {
+    // once the guard leaves the lab the state stays `None`
+    let step = |guard: &Option<(IVec2, Direction)>| {
+        let (pos, dir) = (*guard)?;
+        let next_pos = pos + dir.delta();
+        match lab.obstacles.get(next_pos) {
+            Some(&obstacle) if obstacle || next_pos == extra => Some((pos, dir.turn_right())),
+            Some(_) => Some((next_pos, dir)),
+            None => None,
         }
-    }
+    };
+    brent(Some((init_pos, init_dir)), step).state.is_some()
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-        assert_eq!("6", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "6")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,2 +1,43 @@
+use aoc_common::Solution;
+use aoc_parse::{lines, row, IResult, Span};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, space1},
+    sequence::separated_pair,
+    Parser,
+};
+
 pub mod part1;
 pub mod part2;
+
+/// Test value of a calibration equation and the numbers that must make it.
+pub type Equation = (u64, Vec<u64>);
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<Equation>;
+    type Answer = u64;
+
+    #[tracing::instrument(skip(input))]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Vec<Equation>> {
+    lines(separated_pair(
+        complete::u64,
+        tag(": "),
+        row(complete::u64, space1),
+    ))
+    .parse(input)
+}
@@ -1,13 +1,7 @@
-use miette::miette;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
 use tracing::info;
 
+use crate::Equation;
+
 #[derive(Debug, Clone)]
 enum Ops {
     Add,
@@ -23,11 +17,9 @@ impl Ops {
     }
 }
 
-#[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, equations) = parse_input(input).map_err(|e| miette!("failed to parse {}", e))?;
-
-    Ok(equations
+#[tracing::instrument(skip(equations))]
+pub fn process(equations: &[Equation]) -> u64 {
+    equations
         .iter()
         .map(|(r, v)| {
             if is_solvable(*r, v, &[]) {
@@ -38,8 +30,7 @@ pub fn process(input: &str) -> miette::Result<String> {
                 0
             }
         })
-        .sum::<u64>()
-        .to_string())
+        .sum()
 }
 
 // #[tracing::instrument(ret)]
@@ -65,33 +56,19 @@ fn is_solvable(expected_result: u64, values: &[u64], operations: &[Ops]) -> bool
     false
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
-    separated_list1(
-        line_ending,
-        separated_pair(
-            complete::u64,
-            tag(": "),
-            separated_list1(space1, complete::u64),
-        ),
-    )(input)
-}
-
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-        assert_eq!("3749", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "3749")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
@@ -1,2 +1,57 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::Solution;
+use aoc_grid::{Grid, GridCell};
+use glam::IVec2;
+
 pub mod part1;
 pub mod part2;
+
+/// Antennas by frequency in a city of `n` rows by `m` columns.
+#[derive(Debug, Clone)]
+pub struct City {
+    antennas: HashMap<char, HashSet<IVec2>>,
+    n: i32,
+    m: i32,
+}
+
+/// What the map shows on a position of the city.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('.')]
+    Empty,
+    /// An antenna of the frequency.
+    #[cell(other)]
+    Antenna(char),
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = City;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        let map = Grid::<Tile>::parse_cells(input)?;
+        let mut antennas = HashMap::<_, HashSet<_>>::new();
+        for (pos, &tile) in map.iter() {
+            if let Tile::Antenna(frequency) = tile {
+                antennas.entry(frequency).or_default().insert(pos);
+            }
+        }
+        Ok(City {
+            antennas,
+            n: map.height() as i32,
+            m: map.width() as i32,
+        })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
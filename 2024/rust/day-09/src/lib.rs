@@ -1,2 +1,29 @@
+use aoc_common::Solution;
+use aoc_parse::Span;
+use nom::{character::complete::digit1, Parser};
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    /// The disk map, alternating sizes of files and free space.
+    type Parsed = Vec<usize>;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        let sizes =
+            digit1.map(|digits: Span| digits.bytes().map(|b| usize::from(b - b'0')).collect());
+        Ok(aoc_parse::parse(input, sizes)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
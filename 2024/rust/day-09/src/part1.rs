@@ -1,33 +1,34 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    // let num_files = input.len() / 2;
-    let high_index: u32 = input.chars().map(|c| c.to_digit(10).unwrap()).sum();
-
-    let mut reverse = ((0..input.len()).rev().zip(input.chars().rev()))
-        .scan(high_index, |base_index, (compressed_index, c)| {
-            let num_indices = c.to_digit(10).unwrap();
-            *base_index -= num_indices;
-
-            Some(
-                (*base_index..(*base_index + num_indices))
-                    .rev()
-                    .filter_map(move |i| {
-                        (compressed_index % 2 == 0).then_some((i, compressed_index / 2))
-                    }),
-            )
-        })
+#[tracing::instrument(skip(disk))]
+pub fn process(disk: &[usize]) -> usize {
+    // let num_files = disk.len() / 2;
+    let high_index: usize = disk.iter().sum();
+
+    let mut reverse = ((0..disk.len()).rev().zip(disk.iter().rev()))
+        .scan(
+            high_index,
+            |base_index, (compressed_index, &num_indices)| {
+                *base_index -= num_indices;
+
+                Some(
+                    (*base_index..(*base_index + num_indices))
+                        .rev()
+                        .filter_map(move |i| {
+                            (compressed_index % 2 == 0).then_some((i, compressed_index / 2))
+                        }),
+                )
+            },
+        )
         .flatten();
 
     let mut base_index = 0;
     let mut sum = 0;
-    let mut last_uncompressed_index = u32::MAX;
+    let mut last_uncompressed_index = usize::MAX;
 
-    for (compressed_index, c) in input.chars().enumerate() {
-        let num_indices = c.to_digit(10).unwrap() as usize;
+    for (compressed_index, &num_indices) in disk.iter().enumerate() {
         let file_id = compressed_index / 2;
 
         for uncompressed_index in base_index..(base_index + num_indices) {
-            if uncompressed_index >= last_uncompressed_index as usize {
+            if uncompressed_index >= last_uncompressed_index {
                 break;
             }
             if compressed_index % 2 == 0 {
@@ -42,17 +43,22 @@ pub fn process(input: &str) -> miette::Result<String> {
         base_index += num_indices;
     }
 
-    Ok(sum.to_string())
+    sum
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "2333133121414131402";
-        assert_eq!("1928", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "1928")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
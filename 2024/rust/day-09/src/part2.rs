@@ -1,16 +1,13 @@
 //! This solution was taken from the amazing collection of solutions from
 //! https://github.com/maneatingape
-use nom::ToUsize;
 use std::array::from_fn;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
 const EXTRA: [usize; 10] = [0, 0, 1, 3, 6, 10, 15, 21, 28, 36];
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let disk = parse_input(input);
-
+#[tracing::instrument(skip(disk))]
+pub fn process(disk: &[usize]) -> usize {
     let mut block = 0;
     let mut checksum = 0;
     let mut free: [_; 10] = from_fn(|_| BinaryHeap::with_capacity(1_000));
@@ -55,26 +52,22 @@ pub fn process(input: &str) -> miette::Result<String> {
         }
     }
 
-    Ok(checksum.to_string())
-}
-
-fn parse_input(input: &str) -> Vec<usize> {
-    input
-        .trim()
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .map(|n| n.to_usize())
-        .collect()
+    checksum
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "2333133121414131402";
-        assert_eq!("2858", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "2858")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
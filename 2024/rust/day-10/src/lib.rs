@@ -1,2 +1,53 @@
+use aoc_common::Solution;
+use aoc_grid::Grid;
+use glam::IVec2;
+
 pub mod part1;
 pub mod part2;
+
+/// Topographic map, the altitude of every position.
+#[derive(Debug)]
+pub struct Trail {
+    grid: Grid<u32>,
+}
+
+impl Trail {
+    fn trailheads(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.grid.find_all(|&altitude| altitude == 0)
+    }
+
+    fn is_summit(&self, pos: IVec2) -> bool {
+        self.grid[pos] == 9
+    }
+
+    /// Positions a hike can go to from `pos`, one higher than it.
+    fn hikable_from(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        let altitude = self.grid[pos];
+        self.grid
+            .neighbours4(pos)
+            .filter(move |(_, &next)| next == altitude + 1)
+            .map(|(next, _)| next)
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Trail;
+    type Answer = u32;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(Trail {
+            grid: Grid::try_parse(input, |c| c.to_digit(10))?,
+        })
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
@@ -1,2 +1,27 @@
+use aoc_common::Solution;
+use aoc_parse::row;
+use nom::character::complete::{self, space1};
+
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    /// Numbers engraved on the stones, in order.
+    type Parsed = Vec<u64>;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, row(complete::u64, space1))?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
@@ -22,7 +22,7 @@ impl Stone {
 
     fn split_into_two(&self) -> Option<(Self, Self)> {
         let d = self.count_digits();
-        if d % 2 != 0 || self.0 == 0 {
+        if !d.is_multiple_of(2) || self.0 == 0 {
             return None;
         }
         let first = {
@@ -48,7 +48,7 @@ impl Stone {
     }
 
     fn has_even_digits(&self) -> bool {
-        self.0 != 0 && self.count_digits() % 2 == 0
+        self.0 != 0 && self.count_digits().is_multiple_of(2)
     }
 
     fn blink_n_times(mut stones: Vec<Self>, n: usize) -> Vec<Self> {
@@ -69,33 +69,32 @@ impl Stone {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut stones = input
-        .split_whitespace()
-        .map(|dig| Stone(dig.parse().unwrap()))
-        .collect::<Vec<_>>();
+pub fn process(numbers: &[u64]) -> usize {
+    let stones = numbers.iter().copied().map(Stone).collect::<Vec<_>>();
 
-    stones = Stone::blink_n_times(stones, 25);
-
-    Ok(stones.len().to_string())
+    Stone::blink_n_times(stones, 25).len()
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
     use itertools::Itertools;
+    use rstest::rstest;
 
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "125 17";
-        assert_eq!("55312", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "55312")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 
     #[test]
     #[rustfmt::skip]
-    fn test_examples() -> miette::Result<()> {
+    fn test_blinks() -> miette::Result<()> {
         let initial_arr = vec![125, 17].into_iter().map(Stone).collect_vec();
         let after_1_arr = vec![253000, 1, 7].into_iter().map(Stone).collect_vec();
         let after_2_arr = vec![253, 0, 2024, 14168].into_iter().map(Stone).collect_vec();
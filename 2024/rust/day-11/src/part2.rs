@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use aoc_common::memoize;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 struct Stone(u64);
@@ -6,18 +6,11 @@ struct Stone(u64);
 const N_BLINKS: usize = 75;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut stones = input
-        .split_whitespace()
-        .map(|dig| Stone(dig.parse().unwrap()))
-        .collect::<Vec<_>>();
-
-    let mut cache = HashMap::<_, _>::new();
-    Ok(stones
-        .iter_mut()
-        .map(|s| s.stones_after_blinking_n_times(N_BLINKS, &mut cache))
-        .sum::<usize>()
-        .to_string())
+pub fn process(numbers: &[u64]) -> usize {
+    numbers
+        .iter()
+        .map(|&n| Stone(n).stones_after_blinking_n_times(N_BLINKS))
+        .sum()
 }
 
 impl Stone {
@@ -34,7 +27,7 @@ impl Stone {
 
     fn split_into_two(&self) -> Option<(Self, Self)> {
         let d = self.count_digits();
-        if d % 2 != 0 || self.0 == 0 {
+        if !d.is_multiple_of(2) || self.0 == 0 {
             return None;
         }
         let first = self.0 / 10u64.pow(d / 2);
@@ -47,41 +40,49 @@ impl Stone {
     }
 
     fn has_even_digits(&self) -> bool {
-        self.0 != 0 && self.count_digits() % 2 == 0
+        self.0 != 0 && self.count_digits().is_multiple_of(2)
     }
 
-    fn stones_after_blinking_n_times(
-        &self,
-        n: usize,
-        cache: &mut HashMap<(Stone, usize), usize>,
-    ) -> usize {
+    // stones of every number in the input split into the same few, so the
+    // cache is kept across them
+    #[memoize(thread_local, stats)]
+    fn stones_after_blinking_n_times(&self, n: usize) -> usize {
         if n == 0 {
             return 1;
         }
-        let mut result = 0;
-        if !cache.contains_key(&(self.clone(), n)) {
-            let (first, maybe_second) = self.apply_rule();
-            result += first.stones_after_blinking_n_times(n - 1, cache);
-            if let Some(second) = maybe_second {
-                result += second.stones_after_blinking_n_times(n - 1, cache);
-            }
-            cache.insert((self.clone(), n), result);
-        }
-        *cache.get(&(self.clone(), n)).unwrap()
+        let (first, maybe_second) = self.apply_rule();
+        first.stones_after_blinking_n_times(n - 1)
+            + maybe_second.map_or(0, |second| second.stones_after_blinking_n_times(n - 1))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::{memo, Solution};
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "125 17";
-        assert_eq!("65601038650482", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "65601038650482")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 
+    #[test]
+    fn test_cache_kept_between_stones() {
+        let stats = || memo::stats("stones_after_blinking_n_times").unwrap();
+        assert_eq!(3, Stone(125).stones_after_blinking_n_times(4));
+        let first = stats();
+        assert_eq!(3, Stone(125).stones_after_blinking_n_times(4));
+        let second = stats();
+        assert_eq!(first.misses, second.misses);
+        assert_eq!(first.hits + 1, second.hits);
+    }
+
     #[test]
     fn test_has_even_digits() -> miette::Result<()> {
         assert!(!Stone(0).has_even_digits());
@@ -1,2 +1,49 @@
+use aoc_common::Solution;
+use aoc_parse::{blocks, parse_fmt, IResult, Span};
+use glam::I64Vec2;
+use nom::Parser;
+
 pub mod part1;
 pub mod part2;
+
+/// How far each button moves the claw, and where the prize is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Machine {
+    dx_a: I64Vec2,
+    dx_b: I64Vec2,
+    prize: I64Vec2,
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<Machine>;
+    type Answer = i64;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Vec<Machine>> {
+    blocks(parse_machine_spec).parse(input)
+}
+
+fn parse_machine_spec(input: Span) -> IResult<Machine> {
+    parse_fmt!("Button A: X+{i64}, Y+{i64}\nButton B: X+{i64}, Y+{i64}\nPrize: X={i64}, Y={i64}")
+        .map(|(ax, ay, bx, by, x, y)| Machine {
+            dx_a: I64Vec2::new(ax, ay),
+            dx_b: I64Vec2::new(bx, by),
+            prize: I64Vec2::new(x, y),
+        })
+        .parse(input)
+}
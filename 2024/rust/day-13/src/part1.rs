@@ -1,13 +1,6 @@
 use glam::UVec2;
-use miette::miette;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, terminated, tuple},
-    IResult, Parser,
-};
+
+use crate::Machine;
 
 const MAX_PRESSES: u32 = 200;
 
@@ -35,93 +28,39 @@ impl MachineSpec {
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, machine_specs) =
-        parse_input(input).map_err(|e| miette!("failed to parse input {e}"))?;
+impl From<&Machine> for MachineSpec {
+    fn from(machine: &Machine) -> Self {
+        MachineSpec {
+            dx_a: machine.dx_a.as_uvec2(),
+            dx_b: machine.dx_b.as_uvec2(),
+            prize_pos: machine.prize.as_uvec2(),
+        }
+    }
+}
 
-    Ok(machine_specs
+#[tracing::instrument(skip(machines))]
+pub fn process(machines: &[Machine]) -> i64 {
+    machines
         .iter()
+        .map(MachineSpec::from)
         .filter_map(|s| s.fewest_tokens_to_prize())
-        .sum::<u32>()
-        .to_string())
-}
-
-fn parse_input(input: &str) -> IResult<&str, Vec<MachineSpec>> {
-    separated_list1(line_ending, parse_machine_spec)(input)
-}
-
-fn parse_machine_spec(input: &str) -> IResult<&str, MachineSpec> {
-    map(
-        tuple((
-            terminated(
-                preceded(
-                    tag("Button A: "),
-                    separated_pair(
-                        preceded(tag("X+"), complete::u32),
-                        tag(", "),
-                        preceded(tag("Y+"), complete::u32),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| UVec2::new(x, y)),
-            terminated(
-                preceded(
-                    tag("Button B: "),
-                    separated_pair(
-                        preceded(tag("X+"), complete::u32),
-                        tag(", "),
-                        preceded(tag("Y+"), complete::u32),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| UVec2::new(x, y)),
-            terminated(
-                preceded(
-                    tag("Prize: "),
-                    separated_pair(
-                        preceded(tag("X="), complete::u32),
-                        tag(", "),
-                        preceded(tag("Y="), complete::u32),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| UVec2::new(x, y)),
-        )),
-        |(v1, v2, v3)| MachineSpec {
-            dx_a: v1,
-            dx_b: v2,
-            prize_pos: v3,
-        },
-    )(input)
+        .map(i64::from)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::Solution;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
+    use super::*;
+    use crate::Day;
 
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279
-";
-        assert_eq!("480", process(input)?);
+    #[rstest]
+    #[case::example_1(include_str!("../examples/1.txt"), "480")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }
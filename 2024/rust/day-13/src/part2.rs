@@ -1,13 +1,7 @@
+use aoc_common::math::{non_negative_solutions, solve_linear};
 use glam::I64Vec2;
-use miette::miette;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, terminated, tuple},
-    IResult, Parser,
-};
+
+use crate::Machine;
 
 const OFFSET: I64Vec2 = I64Vec2::new(10_000_000_000_000, 10_000_000_000_000);
 
@@ -20,111 +14,149 @@ struct MachineSpec {
 
 impl MachineSpec {
     fn fewest_tokens_to_prize(&self) -> Option<i64> {
-        let (a_x, a_y) = (self.dx_a.x, self.dx_a.y);
-        let (b_x, b_y) = (self.dx_b.x, self.dx_b.y);
-        let (p_x, p_y) = (self.prize.x, self.prize.y);
+        let (a, b, prize) = (self.dx_a, self.dx_b, self.prize);
+        let matrix = [vec![a.x.into(), b.x.into()], vec![a.y.into(), b.y.into()]];
+        let Some(presses) = solve_linear(&matrix, &[prize.x.into(), prize.y.into()]) else {
+            return self.fewest_tokens_along_line();
+        };
+        let a_presses = i64::try_from(presses[0].to_integer()?).ok()?;
+        let b_presses = i64::try_from(presses[1].to_integer()?).ok()?;
+        (a_presses >= 0 && b_presses >= 0).then_some(a_presses * 3 + b_presses)
+    }
 
-        let a_presses = (p_x * b_y - p_y * b_x) / (a_x * b_y - a_y * b_x);
-        let b_presses = (a_x * p_y - a_y * p_x) / (a_x * b_y - a_y * b_x);
-        if a_presses * a_x + b_presses * b_x == p_x && a_presses * a_y + b_presses * b_y == p_y {
-            Some(a_presses * 3 + b_presses)
+    /// Both buttons move the claw along the same line, so any number of
+    /// ways can reach the prize. A costs more per press, so the cheapest
+    /// way is one of the two with the fewest A or the fewest B presses.
+    ///
+    /// A button that does not move the claw is never worth pressing, the
+    /// other one has to reach the prize on its own. Buttons moving the claw
+    /// opposite ways are left out, the machines only move it forward.
+    fn fewest_tokens_along_line(&self) -> Option<i64> {
+        let (a, b, prize) = (self.dx_a, self.dx_b, self.prize);
+        if a.perp_dot(prize) != 0 || b.perp_dot(prize) != 0 {
+            return None;
+        }
+        if a == I64Vec2::ZERO {
+            return presses_to_reach(b, prize);
+        }
+        if b == I64Vec2::ZERO {
+            return presses_to_reach(a, prize).map(|a_presses| a_presses * 3);
+        }
+        // on a vertical line only the y axis tells the presses apart
+        let (a, b, prize) = if a.x != 0 {
+            (a.x, b.x, prize.x)
         } else {
-            None
+            (a.y, b.y, prize.y)
+        };
+        if a <= 0 || b <= 0 {
+            return None;
         }
+        let solutions = non_negative_solutions(a, b, prize);
+        [solutions.get(0), solutions.last()]
+            .into_iter()
+            .flatten()
+            .map(|(a_presses, b_presses)| a_presses * 3 + b_presses)
+            .min()
     }
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, machine_specs) =
-        parse_input(input).map_err(|e| miette!("failed to parse input {e}"))?;
+/// Presses of `button` alone that move the claw to `prize`, if a
+/// non-negative whole number of them does.
+fn presses_to_reach(button: I64Vec2, prize: I64Vec2) -> Option<i64> {
+    if button == I64Vec2::ZERO {
+        return (prize == I64Vec2::ZERO).then_some(0);
+    }
+    let presses = if button.x != 0 {
+        prize.x / button.x
+    } else {
+        prize.y / button.y
+    };
+    (presses >= 0 && button * presses == prize).then_some(presses)
+}
 
-    Ok(machine_specs
+#[tracing::instrument(skip(machines))]
+pub fn process(machines: &[Machine]) -> i64 {
+    machines
         .iter()
+        .map(|m| MachineSpec {
+            dx_a: m.dx_a,
+            dx_b: m.dx_b,
+            prize: m.prize + OFFSET,
+        })
         .filter_map(|s| s.fewest_tokens_to_prize())
-        .sum::<i64>()
-        .to_string())
-}
-
-fn parse_input(input: &str) -> IResult<&str, Vec<MachineSpec>> {
-    separated_list1(line_ending, parse_machine_spec)(input)
-}
-
-fn parse_machine_spec(input: &str) -> IResult<&str, MachineSpec> {
-    map(
-        tuple((
-            terminated(
-                preceded(
-                    tag("Button A: "),
-                    separated_pair(
-                        preceded(tag("X+"), complete::i64),
-                        tag(", "),
-                        preceded(tag("Y+"), complete::i64),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| I64Vec2::new(x, y)),
-            terminated(
-                preceded(
-                    tag("Button B: "),
-                    separated_pair(
-                        preceded(tag("X+"), complete::i64),
-                        tag(", "),
-                        preceded(tag("Y+"), complete::i64),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| I64Vec2::new(x, y)),
-            terminated(
-                preceded(
-                    tag("Prize: "),
-                    separated_pair(
-                        preceded(tag("X="), complete::i64),
-                        tag(", "),
-                        preceded(tag("Y="), complete::i64),
-                    ),
-                ),
-                line_ending,
-            )
-            .map(|(x, y)| I64Vec2::new(x, y)),
-        )),
-        |(v1, v2, v3)| MachineSpec {
-            dx_a: v1,
-            dx_b: v2,
-            prize: v3 + OFFSET,
-        },
-    )(input)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Solution;
+    use rstest::rstest;
+
     use super::*;
+    use crate::Day;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        // Machine 1 = 280 tokens (A*80 B*40)
-        // Machine 2 = None
-        // Machine 3 = 200 tokens (A*38 B*86)
-        // Machine 4 = None
-        let input = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
+    #[rstest]
+    // Machine 1 = 280 tokens (A*80 B*40)
+    // Machine 2 = None
+    // Machine 3 = 200 tokens (A*38 B*86)
+    // Machine 4 = None
+    #[case::example_1(include_str!("../examples/1.txt"), "875318608908")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
+        Ok(())
+    }
 
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
+    #[test]
+    fn test_collinear_buttons() {
+        // 2 presses of A and 1 of B cost 7, 5 presses of B cost 5
+        let spec = MachineSpec {
+            dx_a: I64Vec2::new(2, 4),
+            dx_b: I64Vec2::new(1, 2),
+            prize: I64Vec2::new(5, 10),
+        };
+        assert_eq!(Some(5), spec.fewest_tokens_to_prize());
+        let spec = MachineSpec {
+            prize: I64Vec2::new(5, 11),
+            ..spec
+        };
+        assert_eq!(None, spec.fewest_tokens_to_prize());
+    }
 
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
+    #[test]
+    fn test_vertical_collinear_buttons() {
+        let spec = MachineSpec {
+            dx_a: I64Vec2::new(0, 2),
+            dx_b: I64Vec2::new(0, 1),
+            prize: I64Vec2::new(0, 5),
+        };
+        assert_eq!(Some(5), spec.fewest_tokens_to_prize());
+        // only B moves the claw, 5 presses of it cost 5
+        let spec = MachineSpec {
+            dx_a: I64Vec2::ZERO,
+            ..spec
+        };
+        assert_eq!(Some(5), spec.fewest_tokens_to_prize());
+    }
 
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279
-";
-        assert_eq!("875318608908", process(input)?);
-        Ok(())
+    #[test]
+    fn test_zero_button() {
+        // only A moves the claw, 5 presses of it cost 15
+        let spec = MachineSpec {
+            dx_a: I64Vec2::new(2, 1),
+            dx_b: I64Vec2::ZERO,
+            prize: I64Vec2::new(10, 5),
+        };
+        assert_eq!(Some(15), spec.fewest_tokens_to_prize());
+        let spec = MachineSpec {
+            prize: I64Vec2::new(9, 4),
+            ..spec
+        };
+        assert_eq!(None, spec.fewest_tokens_to_prize());
+        let spec = MachineSpec {
+            prize: I64Vec2::new(-10, -5),
+            ..spec
+        };
+        assert_eq!(None, spec.fewest_tokens_to_prize());
     }
 }
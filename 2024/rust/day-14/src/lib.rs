@@ -1,2 +1,46 @@
+use aoc_common::Solution;
+use aoc_parse::{lines, FromPuzzle, IResult, Span};
+use glam::IVec2;
+use nom::Parser;
+
 pub mod part1;
 pub mod part2;
+
+#[derive(Debug, PartialEq, Eq, Clone, FromPuzzle)]
+#[puzzle(fmt = "p={pos} v={vel}")]
+pub struct Robot {
+    pos: IVec2,
+    vel: IVec2,
+}
+
+impl Robot {
+    /// Where the robot is after `n` seconds in a space of `size` tiles,
+    /// teleporting around its edges.
+    fn position_after_n_seconds(&self, n: i32, size: IVec2) -> IVec2 {
+        (self.pos + self.vel * n).rem_euclid(size)
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<Robot>;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        Ok(aoc_parse::parse(input, parse_input)?)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Vec<Robot>> {
+    lines(Robot::from_puzzle).parse(input)
+}
@@ -1,2 +1,66 @@
+use aoc_common::Solution;
+use aoc_grid::{Direction, Grid, GridCell};
+use aoc_parse::{grid, lines, row, sections, IResult, Span};
+use miette::miette;
+use nom::{bytes::complete::tag, character::complete::anychar, combinator::map_opt, Parser};
+use nom_supreme::ParserExt;
+
 pub mod part1;
 pub mod part2;
+
+/// What is on a tile of the warehouse, wide boxes only show up once it is
+/// stretched for part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+pub enum Tile {
+    #[cell('.')]
+    Empty,
+    #[cell('#')]
+    Wall,
+    #[cell('O')]
+    Box,
+    #[cell('[')]
+    BoxLeft,
+    #[cell(']')]
+    BoxRight,
+    #[cell('@')]
+    Robot,
+}
+
+/// Map of the warehouse and the moves the robot attempts.
+pub type Warehouse = (Grid<Tile>, Vec<Direction>);
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed = Warehouse;
+    type Answer = usize;
+
+    #[tracing::instrument]
+    fn parse(input: &str) -> miette::Result<Self::Parsed> {
+        let (grid, moves) = aoc_parse::parse(input, parse_input)?;
+        if grid.find(|&tile| tile == Tile::Robot).is_none() {
+            return Err(miette!("the map should have a robot"));
+        }
+        Ok((grid, moves))
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Self::Answer {
+        part1::process(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Self::Answer {
+        part2::process(parsed)
+    }
+}
+
+fn parse_input(input: Span) -> IResult<Warehouse> {
+    sections(grid(Tile::from_char), lines(parse_moves))
+        .map(|(grid, moves)| (grid, moves.concat()))
+        .parse(input)
+}
+
+/// One line of the moves, which go on over many.
+fn parse_moves(input: Span) -> IResult<Vec<Direction>> {
+    let direction = map_opt(anychar, Direction::from_char).context("one of `^>v<`");
+    row(direction, tag("")).parse(input)
+}
@@ -1,68 +1,52 @@
+use aoc_grid::Grid;
 use glam::IVec2;
-use itertools::Itertools;
-use ndarray::Array2;
 use std::mem::swap;
 
-const UP: IVec2 = IVec2::new(0, -1);
-const DOWN: IVec2 = IVec2::new(0, 1);
-const LEFT: IVec2 = IVec2::new(-1, 0);
-const RIGHT: IVec2 = IVec2::new(1, 0);
+use crate::{Tile, Warehouse};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (mut grid, moves) = parse_input(input);
+#[tracing::instrument(skip(map, moves))]
+pub fn process((map, moves): &Warehouse) -> usize {
+    let mut grid = stretch(map);
     let mut position = grid
-        .indexed_iter()
-        .find(|(_, &c)| c == '@')
-        .map(|((y, x), _)| IVec2::new(x as i32, y as i32))
-        .unwrap();
-
-    grid[position.to_index()] = '.';
-
-    for m in &moves {
-        match *m {
-            UP | DOWN => {
-                try_moving_vertically(&mut grid, &mut position, m);
-            }
-            LEFT | RIGHT => {
-                try_moving_horizontally(&mut grid, &mut position, m);
-            }
-            _ => unreachable!(),
+        .find(|&tile| tile == Tile::Robot)
+        .expect("parsing checks the map has a robot");
+
+    grid[position] = Tile::Empty;
+
+    for m in moves {
+        if m.is_vertical() {
+            try_moving_vertically(&mut grid, &mut position, &m.delta());
+        } else {
+            try_moving_horizontally(&mut grid, &mut position, &m.delta());
         }
     }
 
-    println!("{}", grid_to_string(&grid));
+    tracing::debug!("\n{grid}");
 
-    Ok(get_gps_sum(&grid).to_string())
+    get_gps_sum(&grid)
 }
 
-fn get_gps_sum(grid: &Array2<char>) -> usize {
-    grid.indexed_iter()
-        .filter_map(|((row, col), &c)| {
-            if c == '[' {
-                Some(row * 100 + col)
-            } else {
-                None
-            }
-        })
+fn get_gps_sum(grid: &Grid<Tile>) -> usize {
+    grid.find_all(|&tile| tile == Tile::BoxLeft)
+        .map(|pos| (pos.y * 100 + pos.x) as usize)
         .sum()
 }
 
-fn try_moving_horizontally(grid: &mut Array2<char>, start_pos: &mut IVec2, movement: &IVec2) {
+fn try_moving_horizontally(grid: &mut Grid<Tile>, start_pos: &mut IVec2, movement: &IVec2) {
     let mut position = *start_pos + movement;
     let mut size = 1;
 
-    while grid[position.to_index()] != '.' && grid[position.to_index()] != '#' {
+    while grid[position] != Tile::Empty && grid[position] != Tile::Wall {
         position += movement;
         size += 1;
     }
 
-    if grid[position.to_index()] == '.' {
-        let mut previous = '.';
+    if grid[position] == Tile::Empty {
+        let mut previous = Tile::Empty;
         let mut position = *start_pos + movement;
 
         for _ in 0..size {
-            swap(&mut previous, &mut grid[position.to_index()]);
+            swap(&mut previous, &mut grid[position]);
             position += movement;
         }
 
@@ -70,8 +54,8 @@ fn try_moving_horizontally(grid: &mut Array2<char>, start_pos: &mut IVec2, movem
     }
 }
 
-fn try_moving_vertically(grid: &mut Array2<char>, start_pos: &mut IVec2, movement: &IVec2) {
-    if grid[(*start_pos + movement).to_index()] == '.' {
+fn try_moving_vertically(grid: &mut Grid<Tile>, start_pos: &mut IVec2, movement: &IVec2) {
+    if grid[*start_pos + movement] == Tile::Empty {
         *start_pos += movement;
         return;
     }
@@ -85,10 +69,10 @@ fn try_moving_vertically(grid: &mut Array2<char>, start_pos: &mut IVec2, movemen
         let next = todo[index] + movement;
         index += 1;
 
-        let (first, second) = match grid[next.to_index()] {
-            '[' => (next, next + RIGHT),
-            ']' => (next + LEFT, next),
-            '#' => return,
+        let (first, second) = match grid[next] {
+            Tile::BoxLeft => (next, next + IVec2::X),
+            Tile::BoxRight => (next + IVec2::NEG_X, next),
+            Tile::Wall => return,
             _ => continue,
         };
 
@@ -99,125 +83,48 @@ fn try_moving_vertically(grid: &mut Array2<char>, start_pos: &mut IVec2, movemen
     }
 
     for &pos in todo[2..].iter().rev() {
-        grid[(pos + movement).to_index()] = grid[pos.to_index()];
-        grid[pos.to_index()] = '.';
+        grid[pos + movement] = grid[pos];
+        grid[pos] = Tile::Empty;
     }
 
     *start_pos += movement;
 }
 
-trait ToIndex {
-    fn to_index(&self) -> (usize, usize);
-}
+/// Doubles the width of everything but the robot.
+fn stretch(normal_grid: &Grid<Tile>) -> Grid<Tile> {
+    let mut stretched_grid =
+        Grid::filled(normal_grid.width() * 2, normal_grid.height(), Tile::Empty);
 
-impl ToIndex for IVec2 {
-    fn to_index(&self) -> (usize, usize) {
-        (self.y as usize, self.x as usize)
-    }
-}
-
-fn grid_to_string(grid: &Array2<char>) -> String {
-    grid.indexed_iter()
-        .map(|(pos, &c)| {
-            if pos.1 == 0 {
-                "\n".to_string() + c.to_string().as_str()
-            } else {
-                c.to_string()
-            }
-        })
-        .collect()
-}
-
-fn parse_input(input: &str) -> (Array2<char>, Vec<IVec2>) {
-    let (grid_str, moves) = input.split_once("\n\n").unwrap();
-    let moves = moves
-        .lines()
-        .flat_map(|l| {
-            l.chars().map(|c| match c {
-                '^' => UP,
-                '>' => RIGHT,
-                'v' => DOWN,
-                '<' => LEFT,
-                _ => unreachable!(),
-            })
-        })
-        .collect_vec();
-
-    let size = [
-        grid_str.lines().count(),
-        grid_str.lines().next().unwrap().chars().count(),
-    ];
-
-    let normal_grid = grid_str
-        .lines()
-        .flat_map(|line| line.chars().collect_vec())
-        .collect_vec();
-    let normal_grid = Array2::from_shape_vec(size, normal_grid).unwrap();
-
-    println!("{}", grid_to_string(&normal_grid));
-
-    let mut stretched_grid = Array2::from_elem([size[0], size[1] * 2], '.');
-
-    for ((row, col), c) in normal_grid.indexed_iter() {
-        let (left, right) = match c {
-            '#' => ('#', '#'),
-            'O' => ('[', ']'),
-            '@' => ('@', '.'),
+    for (pos, tile) in normal_grid.iter() {
+        let (left, right) = match tile {
+            Tile::Wall => (Tile::Wall, Tile::Wall),
+            Tile::Box => (Tile::BoxLeft, Tile::BoxRight),
+            Tile::Robot => (Tile::Robot, Tile::Empty),
             _ => continue,
         };
-        stretched_grid[(row, col * 2)] = left;
-        stretched_grid[(row, col * 2 + 1)] = right;
+        let pos = IVec2::new(pos.x * 2, pos.y);
+        stretched_grid[pos] = left;
+        stretched_grid[pos + IVec2::X] = right;
     }
-    println!("{}", grid_to_string(&stretched_grid));
+    tracing::debug!("\n{stretched_grid}");
 
-    (stretched_grid, moves)
+    stretched_grid
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_simple() -> miette::Result<()> {
-        let input = "#######
-#...#.#
-#.....#
-#..OO@#
-#..O..#
-#.....#
-#######
-
-<vv<<^^<<^^
-";
-        assert_eq!("618", process(input)?);
-        Ok(())
-    }
+    use aoc_common::Solution;
+    use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
-";
-        assert_eq!("9021", process(input)?);
+    use super::*;
+    use crate::Day;
+
+    #[rstest]
+    #[case::simple(include_str!("../examples/3.txt"), "618")]
+    #[case::example_2(include_str!("../examples/2.txt"), "9021")]
+    #[test_log::test]
+    fn test_examples(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+        assert_eq!(expected, process(&Day::parse(input)?).to_string());
         Ok(())
     }
 }